@@ -13,7 +13,8 @@ use std::io::{self, BufRead, Write};
 use std::str::FromStr;
 use sysinfo::Signal::*;
 use sysinfo::{
-    NetworkExt, NetworksExt, Pid, ProcessExt, ProcessorExt, Signal, System, SystemExt, UserExt,
+    NetworkExt, NetworksExt, Pid, ProcessExt, ProcessSortKey, ProcessorExt, Signal, System,
+    SystemExt, UserExt,
 };
 
 const signals: [Signal; 31] = [
@@ -78,6 +79,11 @@ fn print_help() {
         "show [pid | name]  : show information of the given process \
          corresponding to [pid | name]"
     );
+    writeln!(
+        &mut io::stdout(),
+        "top [key] [n]      : show the [n] (10 by default) first processes \
+         sorted by [key] (cpu, memory, read, write, threads or start)"
+    );
     writeln!(
         &mut io::stdout(),
         "kill [pid] [signal]: send [signal] to the process with this \
@@ -259,6 +265,44 @@ fn interpret_input(input: &str, sys: &mut System) -> bool {
                 }
             }
         }
+        e if e == "top" || e.starts_with("top ") => {
+            let mut args = e.split_whitespace().skip(1);
+            let by = match args.next().unwrap_or("cpu") {
+                "cpu" => Some(ProcessSortKey::CpuUsage),
+                "memory" => Some(ProcessSortKey::Memory),
+                "read" => Some(ProcessSortKey::DiskReadRate),
+                "write" => Some(ProcessSortKey::DiskWriteRate),
+                "threads" => Some(ProcessSortKey::ThreadCount),
+                "start" => Some(ProcessSortKey::StartTime),
+                _ => None,
+            };
+            let n = args.next().map(usize::from_str).unwrap_or(Ok(10));
+            match (by, n) {
+                (Some(by), Ok(n)) => {
+                    for p in sys.top_processes(by, n) {
+                        writeln!(
+                            &mut io::stdout(),
+                            "{:>7} {:<20} cpu={:.1}% memory={} KB read={} B written={} B threads={:?}",
+                            p.pid,
+                            p.name,
+                            p.cpu_usage,
+                            p.memory,
+                            p.disk_usage.read_bytes,
+                            p.disk_usage.written_bytes,
+                            p.thread_count,
+                        );
+                    }
+                }
+                _ => {
+                    writeln!(
+                        &mut io::stdout(),
+                        "top command takes a key (cpu, memory, read, write, threads or start) \
+                         and a number of processes in parameter!"
+                    );
+                    writeln!(&mut io::stdout(), "example: top memory 5");
+                }
+            }
+        }
         "temperature" => {
             for component in sys.get_components() {
                 writeln!(&mut io::stdout(), "{:?}", component);
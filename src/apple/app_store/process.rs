@@ -6,7 +6,7 @@
 
 use std::path::Path;
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal, Uid};
 
 /// Dummy struct representing a process because iOS doesn't support
 /// obtaining process information due to sandboxing.
@@ -79,4 +79,12 @@ impl ProcessExt for Process {
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage::default()
     }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn thread_count(&self) -> Option<usize> {
+        None
+    }
 }
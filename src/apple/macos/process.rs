@@ -12,7 +12,7 @@ use std::borrow::Borrow;
 
 use libc::{c_int, c_void, gid_t, kill, size_t, uid_t};
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal, Uid};
 
 use crate::sys::ffi;
 use crate::sys::process::ThreadStatus;
@@ -221,6 +221,14 @@ impl ProcessExt for Process {
             total_written_bytes: self.written_bytes,
         }
     }
+
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn thread_count(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub(crate) fn compute_cpu_usage(
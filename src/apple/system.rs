@@ -13,7 +13,7 @@ use crate::sys::processor::*;
 #[cfg(target_os = "macos")]
 use core_foundation_sys::base::{kCFAllocatorDefault, CFRelease};
 
-use crate::{LoadAvg, Pid, ProcessorExt, RefreshKind, SystemExt, User};
+use crate::{LoadAvg, Pid, ProcessorExt, RefreshKind, SystemExt, Uid, User};
 
 #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
 use crate::ProcessExt;
@@ -49,6 +49,7 @@ pub struct System {
     networks: Networks,
     port: mach_port_t,
     users: Vec<User>,
+    user_ids: HashMap<Uid, usize>,
     boot_time: u64,
     // Used to get disk information, to be more specific, it's needed by the
     // DADiskCreateFromVolumePath function. Not supported on iOS.
@@ -145,6 +146,7 @@ impl SystemExt for System {
             networks: Networks::new(),
             port,
             users: Vec::new(),
+            user_ids: HashMap::new(),
             boot_time: boot_time(),
             #[cfg(target_os = "macos")]
             session: ffi::SessionWrap(::std::ptr::null_mut()),
@@ -355,6 +357,7 @@ impl SystemExt for System {
 
     fn refresh_users_list(&mut self) {
         self.users = crate::apple::users::get_users_list();
+        self.user_ids = crate::common::get_user_ids(&self.users);
     }
 
     // COMMON PART
@@ -468,6 +471,10 @@ impl SystemExt for System {
         &self.users
     }
 
+    fn get_user_by_id(&self, user_id: Uid) -> Option<&User> {
+        self.user_ids.get(&user_id).map(|pos| &self.users[*pos])
+    }
+
     fn get_boot_time(&self) -> u64 {
         self.boot_time
     }
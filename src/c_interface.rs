@@ -4,6 +4,8 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
+#[cfg(target_os = "linux")]
+use crate::Thread;
 use crate::{NetworkExt, NetworksExt, Process, ProcessExt, ProcessorExt, System, SystemExt};
 use libc::{self, c_char, c_float, c_uint, c_void, pid_t, size_t};
 use std::borrow::BorrowMut;
@@ -13,10 +15,16 @@ use std::ffi::CString;
 pub type CSystem = *mut c_void;
 /// Equivalent of [`Process`][crate::Process] struct.
 pub type CProcess = *const c_void;
+/// Equivalent of [`Thread`][crate::Thread] struct.
+#[cfg(target_os = "linux")]
+pub type CThread = *const c_void;
 /// C string returned from `CString::into_raw`.
 pub type RString = *const c_char;
 /// Callback used by [`get_processes`][crate::System#method.get_processes].
 pub type ProcessLoop = extern "C" fn(pid: pid_t, process: CProcess, data: *mut c_void) -> bool;
+/// Callback used by [`Process::threads`][crate::Process#method.threads].
+#[cfg(target_os = "linux")]
+pub type ThreadLoop = extern "C" fn(tid: pid_t, thread: CThread, data: *mut c_void) -> bool;
 
 /// Equivalent of [`System::new()`][crate::System#method.new].
 #[no_mangle]
@@ -312,32 +320,55 @@ pub extern "C" fn sysinfo_get_process_by_pid(system: CSystem, pid: pid_t) -> CPr
     ret
 }
 
-/// Equivalent of iterating over [`Process::tasks()`][crate::Process#method.tasks].
+/// Equivalent of iterating over [`Process::threads()`][crate::Process#method.threads].
 ///
 /// # /!\ WARNING /!\
 ///
-/// While having this method processes, you should *never* call any refresh method!
+/// While iterating over the threads, you should *never* call any refresh method!
 #[cfg(target_os = "linux")]
 #[no_mangle]
-pub extern "C" fn sysinfo_process_get_tasks(
+pub extern "C" fn sysinfo_process_get_threads(
     process: CProcess,
-    fn_pointer: Option<ProcessLoop>,
+    fn_pointer: Option<ThreadLoop>,
     data: *mut c_void,
 ) -> size_t {
     assert!(!process.is_null());
     if let Some(fn_pointer) = fn_pointer {
         let process = process as *const Process;
-        for (pid, process) in unsafe { (*process).tasks.iter() } {
-            if !fn_pointer(*pid, process as *const Process as CProcess, data) {
+        for (tid, thread) in unsafe { (*process).threads().iter() } {
+            if !fn_pointer(*tid, thread as *const Thread as CThread, data) {
                 break;
             }
         }
-        unsafe { (*process).tasks.len() as size_t }
+        unsafe { (*process).threads().len() as size_t }
     } else {
         0
     }
 }
 
+/// Equivalent of [`Thread::name()`][crate::Thread#method.name].
+#[cfg(target_os = "linux")]
+#[no_mangle]
+pub extern "C" fn sysinfo_thread_get_name(thread: CThread) -> RString {
+    assert!(!thread.is_null());
+    let thread = thread as *const Thread;
+    unsafe {
+        if let Ok(c) = CString::new((*thread).name()) {
+            return c.into_raw() as _;
+        }
+        std::ptr::null()
+    }
+}
+
+/// Equivalent of [`Thread::cpu_usage()`][crate::Thread#method.cpu_usage].
+#[cfg(target_os = "linux")]
+#[no_mangle]
+pub extern "C" fn sysinfo_thread_get_cpu_usage(thread: CThread) -> c_float {
+    assert!(!thread.is_null());
+    let thread = thread as *const Thread;
+    unsafe { (*thread).cpu_usage() }
+}
+
 /// Equivalent of [`Process::pid()`][crate::Process#method.pid].
 #[no_mangle]
 pub extern "C" fn sysinfo_process_get_pid(process: CProcess) -> pid_t {
@@ -4,7 +4,10 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{NetworkData, Networks, NetworksExt, UserExt};
+use crate::{NetworkData, Networks, NetworksExt, Process, ProcessExt, ProcessStatus, UserExt};
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 
 /// Trait to have a common fallback for the [`Pid`][crate::Pid] type.
 pub trait AsU32 {
@@ -358,7 +361,7 @@ pub struct LoadAvg {
 macro_rules! xid {
     ($(#[$outer:meta])+ $name:ident, $type:ty) => {
         $(#[$outer])+
-        #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+        #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
         pub struct $name(pub(crate) $type);
 
         impl std::ops::Deref for $name {
@@ -435,6 +438,16 @@ impl UserExt for User {
     }
 }
 
+/// Returns the position of each user in `users`, indexed by user id.
+#[cfg(not(any(target_os = "unknown", target_arch = "wasm32")))]
+pub(crate) fn get_user_ids(users: &[User]) -> std::collections::HashMap<Uid, usize> {
+    users
+        .iter()
+        .enumerate()
+        .map(|(pos, user)| (user.uid, pos))
+        .collect()
+}
+
 /// Type containing read and written bytes.
 ///
 /// It is returned by [`ProcessExt::disk_usage`][crate::ProcessExt::disk_usage].
@@ -468,3 +481,217 @@ pub struct DiskUsage {
     /// Number of read bytes since the last refresh.
     pub read_bytes: u64,
 }
+
+/// Type containing the resources used by a group of processes.
+///
+/// It is returned by [`SystemExt::get_users_usage`][crate::SystemExt::get_users_usage] and
+/// [`SystemExt::get_process_groups`][crate::SystemExt::get_process_groups].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (name, usage) in s.get_process_groups_by_name() {
+///     println!("{}: {} processes using {} kB", name, usage.nb_processes, usage.memory);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProcessesUsage {
+    /// Sum of the CPU usage of the processes (in %).
+    pub cpu_usage: f32,
+    /// Sum of the memory usage of the processes (in kB).
+    pub memory: u64,
+    /// Sum of the virtual memory usage of the processes (in kB).
+    pub virtual_memory: u64,
+    /// Sum of the disk usage of the processes.
+    pub disk_usage: DiskUsage,
+    /// Number of processes.
+    pub nb_processes: usize,
+    /// Sum of the number of threads of the processes. Processes for which the number of threads
+    /// isn't known (see [`ProcessExt::thread_count`][crate::ProcessExt::thread_count]) are
+    /// counted as having one thread.
+    pub nb_threads: usize,
+}
+
+impl ProcessesUsage {
+    pub(crate) fn add(&mut self, process: &Process) {
+        let disk_usage = process.disk_usage();
+
+        self.cpu_usage += process.cpu_usage();
+        self.memory += process.memory();
+        self.virtual_memory += process.virtual_memory();
+        self.disk_usage.written_bytes += disk_usage.written_bytes;
+        self.disk_usage.total_written_bytes += disk_usage.total_written_bytes;
+        self.disk_usage.read_bytes += disk_usage.read_bytes;
+        self.disk_usage.total_read_bytes += disk_usage.total_read_bytes;
+        self.nb_processes += 1;
+        self.nb_threads += process.thread_count().unwrap_or(1);
+    }
+}
+
+/// Criteria used to select processes in
+/// [`SystemExt::top_processes`][crate::SystemExt::top_processes]. Processes are always returned
+/// in descending order.
+///
+/// ```no_run
+/// use sysinfo::{ProcessSortKey, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for process in s.top_processes(ProcessSortKey::Memory, 5) {
+///     println!("{} ({}): {} kB", process.name, process.pid, process.memory);
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProcessSortKey {
+    /// CPU usage.
+    CpuUsage,
+    /// Memory usage.
+    Memory,
+    /// Number of bytes read since the last refresh. Since all the processes are refreshed at the
+    /// same time, it is the same as sorting by read rate.
+    DiskReadRate,
+    /// Number of bytes written since the last refresh. Since all the processes are refreshed at
+    /// the same time, it is the same as sorting by write rate.
+    DiskWriteRate,
+    /// Number of threads (see [`ProcessExt::thread_count`][crate::ProcessExt::thread_count]).
+    ThreadCount,
+    /// Start time, the most recently started processes come first.
+    StartTime,
+}
+
+/// An owned snapshot of a process' information.
+///
+/// Contrary to [`Process`], it only contains plain values so it can be kept around after the
+/// processes are refreshed or sent to another thread.
+///
+/// It is returned by [`SystemExt::top_processes`][crate::SystemExt::top_processes].
+///
+/// ```no_run
+/// use sysinfo::{ProcessSortKey, System, SystemExt};
+///
+/// let s = System::new_all();
+/// let top = s.top_processes(ProcessSortKey::CpuUsage, 3);
+/// std::thread::spawn(move || {
+///     for process in top {
+///         println!("{:?}", process);
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProcessSummary {
+    /// Process id.
+    pub pid: Pid,
+    /// Parent process id.
+    pub parent: Option<Pid>,
+    /// Name of the process.
+    pub name: String,
+    /// Status of the process.
+    pub status: ProcessStatus,
+    /// CPU usage (in %).
+    pub cpu_usage: f32,
+    /// Memory usage (in kB).
+    pub memory: u64,
+    /// Virtual memory usage (in kB).
+    pub virtual_memory: u64,
+    /// Disk usage.
+    pub disk_usage: DiskUsage,
+    /// Number of threads, if known.
+    pub thread_count: Option<usize>,
+    /// Start time (in seconds since the epoch).
+    pub start_time: u64,
+    /// Id of the user running the process, if known.
+    pub user_id: Option<Uid>,
+}
+
+impl<'a> From<&'a Process> for ProcessSummary {
+    fn from(process: &'a Process) -> ProcessSummary {
+        ProcessSummary {
+            pid: process.pid(),
+            parent: process.parent(),
+            name: process.name().to_owned(),
+            status: process.status(),
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+            virtual_memory: process.virtual_memory(),
+            disk_usage: process.disk_usage(),
+            thread_count: process.thread_count(),
+            start_time: process.start_time(),
+            user_id: process.user_id(),
+        }
+    }
+}
+
+struct TopCandidate<'a> {
+    key: f64,
+    process: &'a Process,
+}
+
+impl<'a> TopCandidate<'a> {
+    fn new(process: &'a Process, by: ProcessSortKey) -> TopCandidate<'a> {
+        let key = match by {
+            ProcessSortKey::CpuUsage => process.cpu_usage() as f64,
+            ProcessSortKey::Memory => process.memory() as f64,
+            ProcessSortKey::DiskReadRate => process.disk_usage().read_bytes as f64,
+            ProcessSortKey::DiskWriteRate => process.disk_usage().written_bytes as f64,
+            ProcessSortKey::ThreadCount => process.thread_count().unwrap_or(1) as f64,
+            ProcessSortKey::StartTime => process.start_time() as f64,
+        };
+        TopCandidate { key, process }
+    }
+}
+
+impl<'a> PartialEq for TopCandidate<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for TopCandidate<'a> {}
+
+impl<'a> PartialOrd for TopCandidate<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for TopCandidate<'a> {
+    // The order is reversed so that the "smallest" candidate is at the top of the `BinaryHeap`.
+    // The pid is used to keep the result stable when keys are equal.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .key
+            .partial_cmp(&self.key)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.process.pid().cmp(&other.process.pid()))
+    }
+}
+
+pub(crate) fn top_processes<'a, I>(
+    processes: I,
+    by: ProcessSortKey,
+    n: usize,
+) -> Vec<ProcessSummary>
+where
+    I: Iterator<Item = &'a Process>,
+{
+    if n == 0 {
+        return Vec::new();
+    }
+    // We only keep the `n` best candidates instead of sorting all the processes.
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+
+    for process in processes {
+        let candidate = TopCandidate::new(process, by);
+        if heap.len() < n {
+            heap.push(candidate);
+        } else if let Some(mut worst) = heap.peek_mut() {
+            if candidate < *worst {
+                *worst = candidate;
+            }
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|candidate| ProcessSummary::from(candidate.process))
+        .collect()
+}
@@ -106,7 +106,17 @@ cfg_if::cfg_if! {
 }
 
 pub use common::{
-    AsU32, DiskType, DiskUsage, Gid, LoadAvg, NetworksIter, Pid, RefreshKind, Signal, Uid, User,
+    AsU32, DiskType, DiskUsage, Gid, LoadAvg, NetworksIter, Pid, ProcessSortKey, ProcessSummary,
+    ProcessesUsage, RefreshKind, Signal, Uid, User,
+};
+pub use query::{ProcessQuery, ProcessQueryIter};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use sys::{
+    current_process, monitor_command, realtime_signal, CgroupLimits, CommandReport, CommandSample,
+    CurrentProcess, DetailedLoadAvg, ElfClass, ExecutableInfo, Interrupt, IoPriority,
+    IoPriorityClass, KernelStats, MemoryInfo, MemoryMap, NumaNode, NumaStats, Pressure,
+    PressureResource, PressureStallKind, PressureTrigger, PressureValues, ProcessMemoryDetails,
+    ResourceUsage, SchedulerStats, Swap, SwapType, Thread, VmStat, ZramStats,
 };
 pub use sys::{Component, Disk, NetworkData, Networks, Process, ProcessStatus, Processor, System};
 pub use traits::{
@@ -121,6 +131,7 @@ pub use utils::get_current_pid;
 mod c_interface;
 mod common;
 mod debug;
+mod query;
 mod system;
 mod traits;
 mod utils;
@@ -256,6 +267,101 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_get_user_by_id() {
+        let mut s = System::new();
+        s.refresh_users_list();
+        for user in s.get_users() {
+            assert_eq!(
+                s.get_user_by_id(user.get_uid()).map(|u| u.get_uid()),
+                Some(user.get_uid())
+            );
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn check_get_process_user() {
+        let mut s = System::new();
+        s.refresh_users_list();
+        s.refresh_processes();
+        let pid = get_current_pid().expect("Failed to get current PID");
+        let process = s.get_process(pid).expect("Failed to get current process");
+        if let Some(user) = s.get_process_user(process) {
+            assert_eq!(Some(user.get_uid()), process.user_id());
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn check_users_usage() {
+        let mut s = System::new();
+        s.refresh_processes();
+        let pid = get_current_pid().expect("Failed to get current PID");
+        let uid = s
+            .get_process(pid)
+            .and_then(|p| p.user_id())
+            .expect("Failed to get current user id");
+        let usages = s.get_users_usage();
+        let usage = usages.get(&uid).expect("Failed to get current user usage");
+        assert!(usage.nb_processes >= 1);
+        assert!(usage.memory > 0);
+        assert_eq!(
+            usages.values().map(|u| u.nb_processes).sum::<usize>(),
+            s.get_processes().len()
+        );
+    }
+
+    #[test]
+    fn check_process_groups() {
+        // We don't want to test on unknown systems.
+        if MIN_USERS > 0 {
+            let mut s = System::new();
+            s.refresh_processes();
+            let pid = get_current_pid().expect("Failed to get current PID");
+            let groups = s.get_process_groups(|p| if p.pid() == pid { Some(true) } else { None });
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[&true].nb_processes, 1);
+
+            let process = s.get_process(pid).expect("Failed to get current process");
+            let groups = s.get_process_groups_by_name();
+            let usage = groups
+                .get(process.name())
+                .expect("Failed to get current process group");
+            assert!(usage.nb_processes >= 1);
+            assert!(usage.nb_threads >= usage.nb_processes);
+            assert!(usage.memory >= process.memory());
+            assert!(s.get_process_groups_by_exe().contains_key(process.exe()));
+        }
+    }
+
+    #[test]
+    fn check_top_processes() {
+        let mut s = System::new();
+        s.refresh_processes();
+        assert!(s.top_processes(ProcessSortKey::Memory, 0).is_empty());
+
+        let nb_processes = s.get_processes().len();
+        let top = s.top_processes(ProcessSortKey::Memory, 5);
+        assert_eq!(top.len(), std::cmp::min(5, nb_processes));
+        assert!(top.windows(2).all(|w| w[0].memory >= w[1].memory));
+
+        let mut memories = s
+            .get_processes()
+            .values()
+            .map(|p| p.memory())
+            .collect::<Vec<_>>();
+        memories.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(
+            top.iter().map(|p| p.memory).collect::<Vec<_>>(),
+            &memories[..top.len()]
+        );
+
+        let all = s.top_processes(ProcessSortKey::StartTime, nb_processes + 1);
+        assert_eq!(all.len(), nb_processes);
+        assert!(all.windows(2).all(|w| w[0].start_time >= w[1].start_time));
+    }
+
     #[test]
     fn check_system_info() {
         // We don't want to test on unknown systems.
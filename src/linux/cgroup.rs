@@ -0,0 +1,264 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::system::get_all_data;
+
+use std::path::{Path, PathBuf};
+
+// On "hybrid" systems, the cgroup v2 hierarchy is mounted in "/sys/fs/cgroup/unified".
+const CGROUP_V2_ROOTS: &[&str] = &["/sys/fs/cgroup/unified", "/sys/fs/cgroup"];
+// The cgroup v1 memory controller reports `PAGE_COUNTER_MAX` (rounded to the page size) when
+// there is no limit.
+const CGROUP_V1_NO_MEMORY_LIMIT: u64 = 0x7FFF_FFFF_FFFF_0000;
+
+/// Resource limits of a control group, read from its cgroup v2 `memory.max`, `memory.current`,
+/// `memory.stat` and `cpu.max` files (or from their cgroup v1 equivalents:
+/// `memory.limit_in_bytes`, `memory.usage_in_bytes`, `memory.stat`, `cpu.cfs_quota_us` and
+/// `cpu.cfs_period_us`).
+///
+/// The limits take the parent control groups into account: the lowest one is returned.
+///
+/// It is returned by [`System::get_cgroup_limits`][crate::System#method.get_cgroup_limits].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_cgroup_limits();
+/// let limits = s.get_cgroup_limits();
+/// if let Some(limit) = limits.memory_limit {
+///     println!("memory: {:?} / {} kB", limits.memory_usage, limit);
+/// }
+/// if let Some(quota) = limits.cpu_quota {
+///     println!("allowed to use {} CPUs", quota);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CgroupLimits {
+    /// Memory limit (in kB), `None` if there is no limit.
+    pub memory_limit: Option<u64>,
+    /// Memory used by the control group (in kB), including the page cache.
+    pub memory_usage: Option<u64>,
+    /// Part of the page cache of the control group (in kB) which can be reclaimed easily (the
+    /// inactive file pages).
+    pub memory_reclaimable: Option<u64>,
+    /// Maximum CPU time the control group can use, as a number of CPUs (`1.5` means one and a
+    /// half CPU), `None` if there is no limit.
+    pub cpu_quota: Option<f64>,
+}
+
+fn get_cgroup_v2_root() -> Option<&'static Path> {
+    CGROUP_V2_ROOTS
+        .iter()
+        .map(Path::new)
+        .find(|root| root.join("cgroup.controllers").exists())
+}
+
+/// Returns the directory of `cgroup` in the cgroup v2 hierarchy.
+pub(crate) fn get_cgroup_v2_path(cgroup: &str) -> Option<PathBuf> {
+    let cgroup = cgroup.trim_start_matches('/');
+    CGROUP_V2_ROOTS
+        .iter()
+        .map(|root| Path::new(root).join(cgroup))
+        .find(|path| path.join("cgroup.controllers").exists())
+}
+
+/// Returns the cgroup path of the current process for `controller`, or in the cgroup v2
+/// hierarchy if `controller` is empty.
+fn parse_self_cgroup<'a>(data: &'a str, controller: &str) -> Option<&'a str> {
+    // Each line looks like this: "hierarchy-ID:controller-list:cgroup-path".
+    data.lines().find_map(|line| {
+        let mut parts = line.splitn(3, ':');
+        let (id, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+        let found = if controller.is_empty() {
+            id == "0" && controllers.is_empty()
+        } else {
+            controllers.split(',').any(|c| c == controller)
+        };
+        if found {
+            Some(path)
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the directory of the control group and the ones of its parents. The directories
+/// which don't exist are skipped: in a container without its own cgroup namespace, the path
+/// of the control group is the one seen from the host while only its own control group is
+/// mounted (as the root).
+fn get_cgroup_dirs(root: &Path, cgroup: &str) -> Vec<PathBuf> {
+    Path::new(cgroup.trim_start_matches('/'))
+        .ancestors()
+        .map(|dir| root.join(dir))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// Reads `file` in all `dirs` and returns the lowest limit returned by `parse` (which gets the
+/// directory and the file content). `None` is returned if the file doesn't exist in any of
+/// them, `Some(None)` if there is no limit.
+fn get_lowest_limit<T: PartialOrd, F: Fn(&Path, &str) -> Option<T>>(
+    dirs: &[PathBuf],
+    file: &str,
+    parse: F,
+) -> Option<Option<T>> {
+    let mut lowest = None;
+
+    for dir in dirs {
+        let data = match get_all_data(dir.join(file), 64) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+        let limit = parse(dir, data.trim());
+        lowest = match (lowest, limit) {
+            (Some(Some(lowest)), Some(limit)) if lowest < limit => Some(Some(lowest)),
+            (Some(Some(lowest)), None) => Some(Some(lowest)),
+            (_, limit) => Some(limit),
+        };
+    }
+    lowest
+}
+
+fn get_usage(dirs: &[PathBuf], file: &str) -> Option<u64> {
+    let data = get_all_data(dirs.first()?.join(file), 64).ok()?;
+    data.trim().parse::<u64>().ok().map(|usage| usage / 1_000)
+}
+
+fn parse_cpu_max(data: &str) -> Option<f64> {
+    // "max 100000" or "[quota] [period]", both in microseconds.
+    let mut parts = data.split_whitespace();
+    let quota = parts.next()?.parse::<f64>().ok()?;
+    let period = parts.next()?.parse::<f64>().ok()?;
+    if period > 0. {
+        Some(quota / period)
+    } else {
+        None
+    }
+}
+
+fn get_memory_stat(dirs: &[PathBuf], key: &str) -> Option<u64> {
+    let data = get_all_data(dirs.first()?.join("memory.stat"), 4096).ok()?;
+    parse_memory_stat(&data, key).map(|value| value / 1_000)
+}
+
+fn parse_memory_stat(data: &str, key: &str) -> Option<u64> {
+    // Each line looks like this: "inactive_file 1253376".
+    data.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? == key {
+            parts.next()?.parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the directories of the control group of the current process (and of its parents)
+/// in the cgroup v2 hierarchy.
+fn get_cgroup_v2_dirs(data: &str) -> Option<Vec<PathBuf>> {
+    match (get_cgroup_v2_root(), parse_self_cgroup(data, "")) {
+        (Some(root), Some(cgroup)) => Some(get_cgroup_dirs(root, cgroup)),
+        _ => None,
+    }
+}
+
+impl CgroupLimits {
+    /// Reads the memory limit and usage of the control group of the current process.
+    pub(crate) fn refresh_memory(&mut self) {
+        self.memory_limit = None;
+        self.memory_usage = None;
+        self.memory_reclaimable = None;
+        let data = match get_all_data("/proc/self/cgroup", 4096) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+        if let Some(dirs) = get_cgroup_v2_dirs(&data) {
+            self.memory_limit = get_lowest_limit(&dirs, "memory.max", |_, v| v.parse().ok())
+                .and_then(|limit| limit)
+                .map(|limit: u64| limit / 1_000);
+            self.memory_usage = get_usage(&dirs, "memory.current");
+            self.memory_reclaimable = get_memory_stat(&dirs, "inactive_file");
+        }
+        // On "hybrid" systems, the controllers are usually still in the cgroup v1 hierarchy.
+        if let (None, Some(cgroup)) = (self.memory_limit, parse_self_cgroup(&data, "memory")) {
+            let dirs = get_cgroup_dirs(Path::new("/sys/fs/cgroup/memory"), cgroup);
+            self.memory_limit = get_lowest_limit(&dirs, "memory.limit_in_bytes", |_, v| {
+                v.parse::<u64>()
+                    .ok()
+                    .filter(|limit| *limit < CGROUP_V1_NO_MEMORY_LIMIT)
+            })
+            .and_then(|limit| limit)
+            .map(|limit| limit / 1_000);
+            if self.memory_usage.is_none() {
+                self.memory_usage = get_usage(&dirs, "memory.usage_in_bytes");
+                self.memory_reclaimable = get_memory_stat(&dirs, "total_inactive_file");
+            }
+        }
+    }
+
+    /// Reads the CPU quota of the control group of the current process.
+    pub(crate) fn refresh_cpu(&mut self) {
+        self.cpu_quota = None;
+        let data = match get_all_data("/proc/self/cgroup", 4096) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+        if let Some(dirs) = get_cgroup_v2_dirs(&data) {
+            self.cpu_quota =
+                get_lowest_limit(&dirs, "cpu.max", |_, v| parse_cpu_max(v)).and_then(|q| q);
+        }
+        if let (None, Some(cgroup)) = (self.cpu_quota, parse_self_cgroup(&data, "cpu")) {
+            let dirs = get_cgroup_dirs(Path::new("/sys/fs/cgroup/cpu"), cgroup);
+            self.cpu_quota = get_lowest_limit(&dirs, "cpu.cfs_quota_us", |dir, quota| {
+                // The quota is "-1" if there is no limit.
+                if quota.starts_with('-') {
+                    return None;
+                }
+                let period = get_all_data(dir.join("cpu.cfs_period_us"), 64).ok()?;
+                parse_cpu_max(&format!("{} {}", quota, period.trim()))
+            })
+            .and_then(|quota| quota);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cpu_max, parse_memory_stat, parse_self_cgroup};
+
+    #[test]
+    fn check_parse_self_cgroup() {
+        let data = "\
+9:name=systemd:/user.slice
+4:memory:/docker/abc
+2:cpu,cpuacct:/docker/def
+0::/user.slice/session-2.scope
+";
+        assert_eq!(parse_self_cgroup(data, "memory"), Some("/docker/abc"));
+        assert_eq!(parse_self_cgroup(data, "cpu"), Some("/docker/def"));
+        assert_eq!(parse_self_cgroup(data, "cpuacct"), Some("/docker/def"));
+        assert_eq!(
+            parse_self_cgroup(data, ""),
+            Some("/user.slice/session-2.scope")
+        );
+        assert_eq!(parse_self_cgroup(data, "pids"), None);
+    }
+
+    #[test]
+    fn check_parse_cpu_max() {
+        assert_eq!(parse_cpu_max("150000 100000"), Some(1.5));
+        assert_eq!(parse_cpu_max("max 100000"), None);
+        assert_eq!(parse_cpu_max("50000 0"), None);
+    }
+
+    #[test]
+    fn check_parse_memory_stat() {
+        let data = "anon 2367488\nfile 4096000\ninactive_file 1253376\nactive_file 2842624\n";
+        assert_eq!(parse_memory_stat(data, "inactive_file"), Some(1253376));
+        assert_eq!(parse_memory_stat(data, "total_inactive_file"), None);
+    }
+}
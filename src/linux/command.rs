@@ -0,0 +1,285 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::utils::timeval_to_duration;
+use crate::{Pid, ProcessExt, System, SystemExt};
+
+use libc::{c_int, rusage, wait4, EINTR, WNOHANG};
+
+use std::fmt;
+use std::io::{self, Read};
+use std::mem::MaybeUninit;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+/// Resources used by a child process, as returned by `wait4` (same as `getrusage` with
+/// `RUSAGE_CHILDREN`). They include the resources used by the children of the child process
+/// which have been waited for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// Time spent in user mode.
+    pub user_time: Duration,
+    /// Time spent in kernel mode.
+    pub system_time: Duration,
+    /// Maximum resident set size (in kB).
+    pub max_resident_memory: u64,
+    /// Number of page faults which didn't require any I/O.
+    pub minor_page_faults: u64,
+    /// Number of page faults which required I/O.
+    pub major_page_faults: u64,
+    /// Number of times the file system had to perform input.
+    pub block_input_operations: u64,
+    /// Number of times the file system had to perform output.
+    pub block_output_operations: u64,
+    /// Number of times the process gave up the processor voluntarily (waiting for a resource).
+    pub voluntary_context_switches: u64,
+    /// Number of times the process was preempted.
+    pub involuntary_context_switches: u64,
+}
+
+impl From<rusage> for ResourceUsage {
+    fn from(usage: rusage) -> ResourceUsage {
+        ResourceUsage {
+            user_time: timeval_to_duration(usage.ru_utime),
+            system_time: timeval_to_duration(usage.ru_stime),
+            // `ru_maxrss` is in KiB.
+            max_resident_memory: usage.ru_maxrss as u64 * 128 / 125,
+            minor_page_faults: usage.ru_minflt as u64,
+            major_page_faults: usage.ru_majflt as u64,
+            block_input_operations: usage.ru_inblock as u64,
+            block_output_operations: usage.ru_oublock as u64,
+            voluntary_context_switches: usage.ru_nvcsw as u64,
+            involuntary_context_switches: usage.ru_nivcsw as u64,
+        }
+    }
+}
+
+/// Information about a monitored command, sampled while it was running.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CommandSample {
+    /// Time elapsed since the command was started.
+    pub elapsed: Duration,
+    /// CPU usage (in %) since the previous sample.
+    pub cpu_usage: f32,
+    /// Memory usage (in kB).
+    pub memory: u64,
+    /// Virtual memory usage (in kB).
+    pub virtual_memory: u64,
+    /// Total number of bytes read.
+    pub total_read_bytes: u64,
+    /// Total number of bytes written.
+    pub total_written_bytes: u64,
+    /// Number of threads.
+    pub nb_threads: usize,
+}
+
+/// Report of a command run with [`monitor_command`][crate::monitor_command].
+///
+/// Its `Display` implementation looks like the output of `/usr/bin/time -v`.
+#[derive(Debug, Clone)]
+pub struct CommandReport {
+    /// Exit status of the command.
+    pub status: ExitStatus,
+    /// Wall clock time of the command.
+    pub elapsed: Duration,
+    /// Resources used by the command, returned by the kernel when it exited.
+    pub resource_usage: ResourceUsage,
+    /// Samples taken while the command was running.
+    pub samples: Vec<CommandSample>,
+}
+
+impl CommandReport {
+    /// Returns the highest memory usage (in kB) observed in the samples.
+    ///
+    /// The peak can be missed if the command ran for less than the sampling interval, look at
+    /// [`ResourceUsage::max_resident_memory`] in this case.
+    pub fn peak_memory(&self) -> u64 {
+        self.samples.iter().map(|s| s.memory).max().unwrap_or(0)
+    }
+
+    /// Returns the highest number of threads observed in the samples.
+    pub fn peak_threads(&self) -> usize {
+        self.samples.iter().map(|s| s.nb_threads).max().unwrap_or(0)
+    }
+
+    /// Returns the total number of bytes read, as observed in the samples.
+    pub fn total_read_bytes(&self) -> u64 {
+        // Once the command exited, the values cannot be read anymore so we can't just take the
+        // last sample.
+        self.samples
+            .iter()
+            .map(|s| s.total_read_bytes)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the total number of bytes written, as observed in the samples.
+    pub fn total_written_bytes(&self) -> u64 {
+        self.samples
+            .iter()
+            .map(|s| s.total_written_bytes)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Display for CommandReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let usage = &self.resource_usage;
+        let cpu_time = usage.user_time + usage.system_time;
+        let elapsed = self.elapsed.as_secs_f64();
+        let cpu_percent = if elapsed > 0. {
+            cpu_time.as_secs_f64() * 100. / elapsed
+        } else {
+            0.
+        };
+        let minutes = self.elapsed.as_secs() / 60;
+
+        writeln!(
+            f,
+            "\tUser time (seconds): {:.2}",
+            usage.user_time.as_secs_f64()
+        )?;
+        writeln!(
+            f,
+            "\tSystem time (seconds): {:.2}",
+            usage.system_time.as_secs_f64()
+        )?;
+        writeln!(f, "\tPercent of CPU this job got: {:.0}%", cpu_percent)?;
+        writeln!(
+            f,
+            "\tElapsed (wall clock) time (h:mm:ss or m:ss): {}:{:05.2}",
+            minutes,
+            elapsed - (minutes * 60) as f64
+        )?;
+        writeln!(
+            f,
+            "\tMaximum resident set size (kbytes): {}",
+            usage.max_resident_memory
+        )?;
+        writeln!(
+            f,
+            "\tMajor (requiring I/O) page faults: {}",
+            usage.major_page_faults
+        )?;
+        writeln!(
+            f,
+            "\tMinor (reclaiming a frame) page faults: {}",
+            usage.minor_page_faults
+        )?;
+        writeln!(
+            f,
+            "\tVoluntary context switches: {}",
+            usage.voluntary_context_switches
+        )?;
+        writeln!(
+            f,
+            "\tInvoluntary context switches: {}",
+            usage.involuntary_context_switches
+        )?;
+        writeln!(f, "\tFile system inputs: {}", usage.block_input_operations)?;
+        writeln!(
+            f,
+            "\tFile system outputs: {}",
+            usage.block_output_operations
+        )?;
+        writeln!(f, "\tPeak sampled memory (kbytes): {}", self.peak_memory())?;
+        writeln!(f, "\tPeak sampled threads: {}", self.peak_threads())?;
+        writeln!(f, "\tBytes read: {}", self.total_read_bytes())?;
+        writeln!(f, "\tBytes written: {}", self.total_written_bytes())?;
+        match self.status.code() {
+            Some(code) => write!(f, "\tExit status: {}", code),
+            None => write!(
+                f,
+                "\tCommand terminated by signal {}",
+                self.status.signal().unwrap_or(0)
+            ),
+        }
+    }
+}
+
+/// Spawns `command` and monitors it until it exits, taking a sample of its resources usage
+/// every `interval`.
+///
+/// The child process is reaped by this function so the resources it used (including the ones
+/// of its own children which it waited for) can be retrieved: don't try to wait for it in
+/// another thread.
+///
+/// If the standard output or error of `command` are piped, what the command writes there is
+/// read and discarded so it doesn't block on a full pipe (redirect them to a file to keep
+/// them). A piped standard input is closed.
+///
+/// ```no_run
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// let mut command = Command::new("cc");
+/// command.args(&["-c", "main.c"]);
+/// let report = sysinfo::monitor_command(&mut command, Duration::from_millis(100))
+///     .expect("failed to run the compiler");
+/// println!("{}", report);
+/// println!("peak memory: {} kB", report.resource_usage.max_resident_memory);
+/// ```
+pub fn monitor_command(command: &mut Command, interval: Duration) -> io::Result<CommandReport> {
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let pid = child.id() as Pid;
+    drop(child.stdin.take());
+    if let Some(stdout) = child.stdout.take() {
+        discard(stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        discard(stderr);
+    }
+    let mut system = System::new();
+    let mut samples = Vec::new();
+
+    loop {
+        if system.refresh_process(pid) {
+            if let Some(process) = system.get_process(pid) {
+                let disk_usage = process.disk_usage();
+                samples.push(CommandSample {
+                    elapsed: start.elapsed(),
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    virtual_memory: process.virtual_memory(),
+                    total_read_bytes: disk_usage.total_read_bytes,
+                    total_written_bytes: disk_usage.total_written_bytes,
+                    nb_threads: process.threads().len(),
+                });
+            }
+        }
+
+        let mut status: c_int = 0;
+        let mut usage = MaybeUninit::<rusage>::zeroed();
+        match unsafe { wait4(pid, &mut status, WNOHANG, usage.as_mut_ptr()) } {
+            0 => std::thread::sleep(interval),
+            ret if ret == pid => {
+                return Ok(CommandReport {
+                    status: ExitStatus::from_raw(status),
+                    elapsed: start.elapsed(),
+                    resource_usage: ResourceUsage::from(unsafe { usage.assume_init() }),
+                    samples,
+                });
+            }
+            _ => {
+                let err = io::Error::last_os_error();
+                if err.raw_os_error() != Some(EINTR) {
+                    // We don't want to leave a running (or zombie) process behind.
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+// Reads `pipe` until it is closed in another thread.
+fn discard<R: Read + Send + 'static>(mut pipe: R) {
+    std::thread::spawn(move || io::copy(&mut pipe, &mut io::sink()));
+}
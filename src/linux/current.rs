@@ -0,0 +1,110 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::system::get_all_data;
+use crate::utils::timeval_to_duration;
+
+use libc::{getrusage, rusage, RUSAGE_SELF};
+
+use std::fs;
+use std::mem::MaybeUninit;
+use std::time::Duration;
+
+/// Snapshot of the resources used by the current process.
+///
+/// It is returned by [`current_process`][crate::current_process].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentProcess {
+    /// Time spent in user mode.
+    pub user_time: Duration,
+    /// Time spent in kernel mode.
+    pub system_time: Duration,
+    /// Resident memory (in kB).
+    pub memory: u64,
+    /// Peak resident memory (in kB).
+    pub peak_memory: u64,
+    /// Virtual memory (in kB).
+    pub virtual_memory: u64,
+    /// Number of open file descriptors.
+    pub nb_fds: usize,
+    /// Number of threads.
+    pub nb_threads: usize,
+    /// Number of bytes read from the storage layer since the process started.
+    pub total_read_bytes: u64,
+    /// Number of bytes written to the storage layer since the process started.
+    pub total_written_bytes: u64,
+}
+
+fn parse_kb(value: &str) -> u64 {
+    // The value looks like this: "    3348 kB". It is actually in KiB so it is converted.
+    value
+        .split_whitespace()
+        .next()
+        .and_then(|x| x.parse::<u64>().ok())
+        .map(|x| x * 128 / 125)
+        .unwrap_or(0)
+}
+
+/// Returns a snapshot of the resources used by the current process.
+///
+/// Contrary to [`SystemExt::refresh_process`][crate::SystemExt::refresh_process], it doesn't
+/// require a [`System`][crate::System]: only a few files in `/proc/self` are read (and
+/// `getrusage` is called), which makes it cheap enough to be called very often. `None` is
+/// returned if `/proc/self/status` cannot be read.
+///
+/// ```no_run
+/// let snapshot = sysinfo::current_process().expect("failed to get current process");
+/// println!(
+///     "cpu time: {:?}, memory: {} kB, {} threads, {} fds",
+///     snapshot.user_time + snapshot.system_time,
+///     snapshot.memory,
+///     snapshot.nb_threads,
+///     snapshot.nb_fds,
+/// );
+/// ```
+pub fn current_process() -> Option<CurrentProcess> {
+    let mut current = CurrentProcess::default();
+
+    let mut usage = MaybeUninit::<rusage>::zeroed();
+    if unsafe { getrusage(RUSAGE_SELF, usage.as_mut_ptr()) } == 0 {
+        let usage = unsafe { usage.assume_init() };
+        current.user_time = timeval_to_duration(usage.ru_utime);
+        current.system_time = timeval_to_duration(usage.ru_stime);
+    }
+
+    let status = get_all_data("/proc/self/status", 16_384).ok()?;
+    for line in status.lines() {
+        let mut parts = line.splitn(2, ':');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key, value),
+            _ => continue,
+        };
+        match key {
+            "VmRSS" => current.memory = parse_kb(value),
+            "VmHWM" => current.peak_memory = parse_kb(value),
+            "VmSize" => current.virtual_memory = parse_kb(value),
+            "Threads" => current.nb_threads = value.trim().parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    if let Ok(io) = get_all_data("/proc/self/io", 1024) {
+        for line in io.lines() {
+            let mut parts = line.split(": ");
+            match (parts.next(), parts.next().and_then(|x| x.parse().ok())) {
+                (Some("read_bytes"), Some(value)) => current.total_read_bytes = value,
+                (Some("write_bytes"), Some(value)) => current.total_written_bytes = value,
+                _ => {}
+            }
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir("/proc/self/fd") {
+        // The file descriptor used to read the directory is listed as well.
+        current.nb_fds = entries.count().saturating_sub(1);
+    }
+    Some(current)
+}
@@ -0,0 +1,310 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+const PT_INTERP: u32 = 3;
+const PT_NOTE: u32 = 4;
+const NT_GNU_BUILD_ID: u32 = 3;
+// We don't want to read huge segments if the file is corrupted.
+const MAX_SEGMENT_SIZE: u64 = 65_536;
+
+/// ELF class of an executable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElfClass {
+    /// 32-bit executable.
+    Elf32,
+    /// 64-bit executable.
+    Elf64,
+}
+
+/// Information about the executable of a process, read from its ELF headers.
+///
+/// It is returned by [`Process::exe_info`][crate::Process#method.exe_info].
+///
+/// ```no_run
+/// use sysinfo::{ElfClass, ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for process in s.get_processes().values() {
+///     if let Some(info) = process.exe_info() {
+///         if info.class == ElfClass::Elf32 {
+///             println!("{} is a 32-bit {} binary", process.name(), info.architecture());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutableInfo {
+    /// ELF class (32 or 64-bit).
+    pub class: ElfClass,
+    /// Target machine (the `e_machine` field of the ELF header).
+    pub machine: u16,
+    /// The executable doesn't require an interpreter (dynamic loader).
+    pub statically_linked: bool,
+    /// Path of the interpreter (dynamic loader), such as `/lib64/ld-linux-x86-64.so.2`.
+    pub interpreter: Option<PathBuf>,
+    /// GNU build-id of the executable, as an hexadecimal string.
+    pub build_id: Option<String>,
+}
+
+impl ExecutableInfo {
+    /// Returns the name of the target architecture, using the same names as
+    /// [`std::env::consts::ARCH`], or `"unknown"`.
+    pub fn architecture(&self) -> &'static str {
+        let is_64 = self.class == ElfClass::Elf64;
+        match self.machine {
+            2 | 43 if is_64 => "sparc64",
+            2 => "sparc",
+            3 => "x86",
+            4 => "m68k",
+            8 if is_64 => "mips64",
+            8 => "mips",
+            20 => "powerpc",
+            21 => "powerpc64",
+            22 if is_64 => "s390x",
+            22 => "s390",
+            40 => "arm",
+            62 => "x86_64",
+            183 => "aarch64",
+            243 if is_64 => "riscv64",
+            243 => "riscv32",
+            258 => "loongarch64",
+            _ => "unknown",
+        }
+    }
+}
+
+struct Reader {
+    class: ElfClass,
+    little_endian: bool,
+}
+
+impl Reader {
+    fn u16(&self, data: &[u8], pos: usize) -> Option<u16> {
+        let bytes = [*data.get(pos)?, *data.get(pos + 1)?];
+        Some(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    fn u32(&self, data: &[u8], pos: usize) -> Option<u32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(data.get(pos..pos + 4)?);
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn u64(&self, data: &[u8], pos: usize) -> Option<u64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(data.get(pos..pos + 8)?);
+        Some(if self.little_endian {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        })
+    }
+
+    // Reads an address-sized value.
+    fn addr(&self, data: &[u8], pos: usize) -> Option<u64> {
+        match self.class {
+            ElfClass::Elf32 => self.u32(data, pos).map(u64::from),
+            ElfClass::Elf64 => self.u64(data, pos),
+        }
+    }
+}
+
+fn read_at<R: Read + Seek>(file: &mut R, offset: u64, size: u64) -> Option<Vec<u8>> {
+    if size > MAX_SEGMENT_SIZE {
+        return None;
+    }
+    let mut data = vec![0; size as usize];
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    file.read_exact(&mut data).ok()?;
+    Some(data)
+}
+
+fn parse_build_id(reader: &Reader, notes: &[u8]) -> Option<String> {
+    let align = |x: usize| (x + 3) & !3;
+    let mut pos = 0;
+
+    // Each note is made of its name size, its description size, its type, then the name and
+    // description (both padded to 4 bytes).
+    while pos + 12 <= notes.len() {
+        let name_size = reader.u32(notes, pos)? as usize;
+        let desc_size = reader.u32(notes, pos + 4)? as usize;
+        let kind = reader.u32(notes, pos + 8)?;
+        let name_start = pos + 12;
+        let desc_start = name_start.checked_add(align(name_size))?;
+        let desc = notes.get(desc_start..desc_start.checked_add(desc_size)?)?;
+
+        if kind == NT_GNU_BUILD_ID && notes.get(name_start..name_start + name_size)? == b"GNU\0" {
+            return Some(desc.iter().map(|b| format!("{:02x}", b)).collect());
+        }
+        pos = desc_start + align(desc_size);
+    }
+    None
+}
+
+pub(crate) fn parse_executable<R: Read + Seek>(file: &mut R) -> Option<ExecutableInfo> {
+    let header = read_at(file, 0, 64)?;
+    if header[..4] != *b"\x7fELF" {
+        return None;
+    }
+    let class = match header[4] {
+        1 => ElfClass::Elf32,
+        2 => ElfClass::Elf64,
+        _ => return None,
+    };
+    let reader = Reader {
+        class,
+        little_endian: header[5] == 1,
+    };
+    let machine = reader.u16(&header, 18)?;
+    let (ph_offset, ph_entry_size, ph_count) = match class {
+        ElfClass::Elf32 => (
+            reader.addr(&header, 28)?,
+            reader.u16(&header, 42)?,
+            reader.u16(&header, 44)?,
+        ),
+        ElfClass::Elf64 => (
+            reader.addr(&header, 32)?,
+            reader.u16(&header, 54)?,
+            reader.u16(&header, 56)?,
+        ),
+    };
+    let min_entry_size = match class {
+        ElfClass::Elf32 => 32,
+        ElfClass::Elf64 => 56,
+    };
+    if ph_count > 0 && ph_entry_size < min_entry_size {
+        return None;
+    }
+    let program_headers = read_at(
+        file,
+        ph_offset,
+        u64::from(ph_entry_size) * u64::from(ph_count),
+    )?;
+
+    let mut info = ExecutableInfo {
+        class,
+        machine,
+        statically_linked: true,
+        interpreter: None,
+        build_id: None,
+    };
+    for entry in program_headers.chunks_exact(ph_entry_size.max(1) as usize) {
+        let kind = reader.u32(entry, 0)?;
+        let (offset, size) = match class {
+            ElfClass::Elf32 => (reader.addr(entry, 4)?, reader.addr(entry, 16)?),
+            ElfClass::Elf64 => (reader.addr(entry, 8)?, reader.addr(entry, 32)?),
+        };
+        match kind {
+            PT_INTERP => {
+                info.statically_linked = false;
+                if let Some(data) = read_at(file, offset, size) {
+                    let data = data.split(|c| *c == 0).next().unwrap_or(&[]);
+                    info.interpreter = Some(PathBuf::from(std::ffi::OsStr::from_bytes(data)));
+                }
+            }
+            PT_NOTE if info.build_id.is_none() => {
+                info.build_id =
+                    read_at(file, offset, size).and_then(|notes| parse_build_id(&reader, &notes));
+            }
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+pub(crate) fn get_executable_info(path: &Path) -> Option<ExecutableInfo> {
+    // `/proc/[pid]/exe` gives access to the executable even if it has been deleted.
+    let mut file = File::open(path.join("exe")).ok()?;
+    parse_executable(&mut file)
+}
+
+pub(crate) fn is_executable_deleted(path: &Path) -> bool {
+    let exe = match fs::read_link(path.join("exe")) {
+        Ok(exe) => exe,
+        Err(_) => return false,
+    };
+    if exe.as_os_str().as_bytes().ends_with(b" (deleted)") {
+        return true;
+    }
+    // The file might have been replaced (by a package upgrade for example): in this case, the
+    // executable isn't the file at this path anymore. We go through `/proc/[pid]/root` in case
+    // the process runs in another mount namespace.
+    let on_disk = match exe.strip_prefix("/") {
+        Ok(relative) => fs::metadata(path.join("root").join(relative)),
+        Err(_) => return false,
+    };
+    match (on_disk, fs::metadata(path.join("exe"))) {
+        (Ok(on_disk), Ok(running)) => {
+            on_disk.dev() != running.dev() || on_disk.ino() != running.ino()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_executable, ElfClass};
+    use std::io::Cursor;
+
+    #[test]
+    fn check_parse_executable() {
+        // A big endian 32-bit PowerPC executable with an interpreter and a build-id.
+        let mut data = vec![0; 200];
+        data[..7].copy_from_slice(b"\x7fELF\x01\x02\x01");
+        data[18..20].copy_from_slice(&20u16.to_be_bytes());
+        // Program headers: offset, entry size and count.
+        data[28..32].copy_from_slice(&52u32.to_be_bytes());
+        data[42..44].copy_from_slice(&32u16.to_be_bytes());
+        data[44..46].copy_from_slice(&2u16.to_be_bytes());
+        // PT_INTERP
+        data[52..56].copy_from_slice(&3u32.to_be_bytes());
+        data[56..60].copy_from_slice(&120u32.to_be_bytes());
+        data[68..72].copy_from_slice(&14u32.to_be_bytes());
+        // PT_NOTE
+        data[84..88].copy_from_slice(&4u32.to_be_bytes());
+        data[88..92].copy_from_slice(&140u32.to_be_bytes());
+        data[100..104].copy_from_slice(&20u32.to_be_bytes());
+        data[120..134].copy_from_slice(b"/lib/ld.so.1\0\0");
+        data[140..144].copy_from_slice(&4u32.to_be_bytes());
+        data[144..148].copy_from_slice(&4u32.to_be_bytes());
+        data[148..152].copy_from_slice(&3u32.to_be_bytes());
+        data[152..156].copy_from_slice(b"GNU\0");
+        data[156..160].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let info = parse_executable(&mut Cursor::new(&data)).expect("failed to parse");
+        assert_eq!(info.class, ElfClass::Elf32);
+        assert_eq!(info.architecture(), "powerpc");
+        assert!(!info.statically_linked);
+        assert_eq!(
+            info.interpreter.as_ref().and_then(|p| p.to_str()),
+            Some("/lib/ld.so.1")
+        );
+        assert_eq!(info.build_id.as_deref(), Some("deadbeef"));
+
+        // Without program headers, the executable is static.
+        data[44..46].copy_from_slice(&0u16.to_be_bytes());
+        let info = parse_executable(&mut Cursor::new(&data)).expect("failed to parse");
+        assert!(info.statically_linked);
+        assert!(info.interpreter.is_none() && info.build_id.is_none());
+
+        data[0] = 0;
+        assert!(parse_executable(&mut Cursor::new(&data)).is_none());
+    }
+}
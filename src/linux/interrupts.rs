@@ -0,0 +1,257 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::processor::Processor;
+use crate::ProcessorExt;
+
+use std::collections::HashMap;
+
+/// Counters of an interrupt (from `/proc/interrupts`) or of a softirq (from `/proc/softirqs`)
+/// on each processor.
+///
+/// The counters are indexed like [`SystemExt::get_processors`][crate::SystemExt::get_processors]
+/// and are given since boot (`get_total_*`) or since the previous call to
+/// [`System::refresh_interrupts`][crate::System#method.refresh_interrupts].
+///
+/// ```no_run
+/// use sysinfo::{ProcessorExt, System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_interrupts();
+/// std::thread::sleep(std::time::Duration::from_secs(1));
+/// s.refresh_interrupts();
+/// for interrupt in s.get_interrupts().iter().filter(|i| i.get_device().contains("eth0")) {
+///     for (processor, count) in s.get_processors().iter().zip(interrupt.get_counts()) {
+///         println!("{} {}: {}", interrupt.get_device(), processor.get_name(), count);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interrupt {
+    name: String,
+    chip: String,
+    hw_irq: String,
+    device: String,
+    counts: Vec<u64>,
+    new_counts: Vec<u64>,
+}
+
+impl Interrupt {
+    /// Returns the name of the interrupt: its number (`"24"`), an architecture specific name
+    /// (`"LOC"`, `"NMI"`, etc) or the name of the softirq (`"NET_RX"`, `"TIMER"`, etc).
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the interrupt controller (`"IO-APIC"`, `"PCI-MSIX-0000:00:01.0"`,
+    /// etc). It is empty for the architecture specific interrupts and for softirqs.
+    pub fn get_chip(&self) -> &str {
+        &self.chip
+    }
+
+    /// Returns the hardware interrupt number in the controller, with the trigger type if the
+    /// kernel reports it (`"5-edge"` for example). It is empty for the architecture specific
+    /// interrupts and for softirqs.
+    pub fn get_hw_irq(&self) -> &str {
+        &self.hw_irq
+    }
+
+    /// Returns the names of the devices using the interrupt (`"virtio3-input.0"`), or the
+    /// description of the architecture specific interrupts (`"Local timer interrupts"`). It is
+    /// empty for softirqs.
+    pub fn get_device(&self) -> &str {
+        &self.device
+    }
+
+    /// Returns the number of interrupts serviced by each processor since the last refresh.
+    pub fn get_counts(&self) -> &[u64] {
+        &self.new_counts
+    }
+
+    /// Returns the number of interrupts serviced by each processor since boot.
+    pub fn get_total_counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the number of interrupts serviced by all processors since the last refresh.
+    pub fn get_count(&self) -> u64 {
+        self.new_counts.iter().sum()
+    }
+
+    /// Returns the number of interrupts serviced by all processors since boot.
+    pub fn get_total_count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// Parses `/proc/interrupts` or `/proc/softirqs`. The counters are ordered like `processors`
+/// and `new_counts` is computed from the counters in `previous`.
+pub(crate) fn parse_interrupts(
+    data: &str,
+    processors: &[Processor],
+    previous: &[Interrupt],
+) -> Vec<Interrupt> {
+    let mut lines = data.lines();
+    // The header gives the CPUs of each column ("CPU0 CPU1 CPU3"), offline CPUs are skipped.
+    let columns = match lines.next() {
+        Some(header) => header
+            .split_whitespace()
+            .map(|cpu| cpu.to_lowercase())
+            .collect::<Vec<_>>(),
+        None => return Vec::new(),
+    };
+    // The position of each processor in the columns.
+    let positions = processors
+        .iter()
+        .map(|p| columns.iter().position(|c| c == p.get_name()))
+        .collect::<Vec<_>>();
+    let previous = previous
+        .iter()
+        .map(|i| (i.name.as_str(), &i.counts))
+        .collect::<HashMap<_, _>>();
+
+    lines
+        .filter_map(|line| {
+            // A line looks like this:
+            // " 36:      39964       1234 PCI-MSIX-0000:00:02.0   1-edge      virtio1-req.0"
+            let mut parts = line.trim_start().splitn(2, ':');
+            let name = parts.next()?.trim();
+            let mut rest = parts.next()?.trim_start();
+            let mut values = Vec::with_capacity(columns.len());
+
+            // Some lines, like "ERR", only have one value.
+            while values.len() < columns.len() {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                match rest[..end].parse::<u64>() {
+                    Ok(value) => values.push(value),
+                    Err(_) => break,
+                }
+                rest = rest[end..].trim_start();
+            }
+            let counts = positions
+                .iter()
+                .map(|pos| pos.and_then(|pos| values.get(pos).copied()).unwrap_or(0))
+                .collect::<Vec<_>>();
+
+            let (chip, hw_irq, device) = if name.bytes().all(|c| c.is_ascii_digit()) {
+                let mut tokens = rest.split_whitespace().peekable();
+                let chip = tokens.next().unwrap_or("").to_owned();
+                let mut hw_irq = String::new();
+                // The hardware interrupt number can be followed by the trigger type, separated
+                // by a space or a dash ("27 Level" or "5-edge").
+                if let Some(token) = tokens.peek() {
+                    if token.starts_with(|c: char| c.is_ascii_digit()) {
+                        hw_irq.push_str(token);
+                        tokens.next();
+                        if let Some(trigger) = tokens.peek() {
+                            if *trigger == "Level" || *trigger == "Edge" {
+                                hw_irq.push(' ');
+                                hw_irq.push_str(trigger);
+                                tokens.next();
+                            }
+                        }
+                    }
+                }
+                (chip, hw_irq, tokens.collect::<Vec<_>>().join(" "))
+            } else {
+                (String::new(), String::new(), rest.trim().to_owned())
+            };
+            let new_counts = match previous.get(name) {
+                Some(old) if old.len() == counts.len() => counts
+                    .iter()
+                    .zip(old.iter())
+                    .map(|(new, old)| new.saturating_sub(*old))
+                    .collect(),
+                _ => vec![0; counts.len()],
+            };
+            Some(Interrupt {
+                name: name.to_owned(),
+                chip,
+                hw_irq,
+                device,
+                counts,
+                new_counts,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_interrupts;
+    use crate::sys::processor::Processor;
+
+    fn processor(name: &str) -> Processor {
+        Processor::new_with_values(
+            name,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+            String::new(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn check_parse_interrupts() {
+        // CPU2 is offline.
+        let processors = [processor("cpu0"), processor("cpu1"), processor("cpu3")];
+        let data = "\
+           CPU0       CPU1       CPU3
+ 24:          1          2          3  IO-APIC   5-edge      ACPI:Ged
+ 36:      39964         10          0  PCI-MSIX-0000:00:02.0   1-edge      virtio1-req.0
+ 11:          7          0          1  GICv3  27 Level     arch_timer
+ 42:          0          0          0  IR-PCI-MSI 327680-edge      0000:00:14.0 xhci_hcd
+LOC:     281888     200000     100000   Local timer interrupts
+ERR:          0
+";
+        let interrupts = parse_interrupts(data, &processors, &[]);
+        assert_eq!(interrupts.len(), 6);
+        let irq = &interrupts[1];
+        assert_eq!(irq.get_name(), "36");
+        assert_eq!(irq.get_chip(), "PCI-MSIX-0000:00:02.0");
+        assert_eq!(irq.get_hw_irq(), "1-edge");
+        assert_eq!(irq.get_device(), "virtio1-req.0");
+        assert_eq!(irq.get_total_counts(), &[39964, 10, 0]);
+        assert_eq!(irq.get_total_count(), 39974);
+        assert_eq!(irq.get_counts(), &[0, 0, 0]);
+        assert_eq!(interrupts[2].get_chip(), "GICv3");
+        assert_eq!(interrupts[2].get_hw_irq(), "27 Level");
+        assert_eq!(interrupts[2].get_device(), "arch_timer");
+        // The device name starting with a digit isn't part of the hardware interrupt number.
+        assert_eq!(interrupts[3].get_hw_irq(), "327680-edge");
+        assert_eq!(interrupts[3].get_device(), "0000:00:14.0 xhci_hcd");
+        assert_eq!(interrupts[4].get_name(), "LOC");
+        assert_eq!(interrupts[4].get_chip(), "");
+        assert_eq!(interrupts[4].get_device(), "Local timer interrupts");
+        assert_eq!(interrupts[5].get_total_counts(), &[0, 0, 0]);
+
+        let data = "\
+           CPU0       CPU1       CPU3
+ 36:      40000         15          2  PCI-MSIX-0000:00:02.0   1-edge      virtio1-req.0
+";
+        let interrupts = parse_interrupts(data, &processors, &interrupts);
+        assert_eq!(interrupts[0].get_counts(), &[36, 5, 2]);
+        assert_eq!(interrupts[0].get_count(), 43);
+
+        let softirqs = parse_interrupts(
+            "                    CPU0       CPU1       CPU3\n      NET_RX:       4010   1   2\n",
+            &processors,
+            &[],
+        );
+        assert_eq!(softirqs[0].get_name(), "NET_RX");
+        assert_eq!(softirqs[0].get_device(), "");
+        assert_eq!(softirqs[0].get_total_counts(), &[4010, 1, 2]);
+    }
+}
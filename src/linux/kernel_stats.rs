@@ -0,0 +1,227 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use std::time::{Duration, Instant};
+
+/// Kernel activity counters, read from the lines following the `cpu` ones in `/proc/stat` by
+/// [`SystemExt::refresh_cpu`][crate::SystemExt::refresh_cpu]. The counters are available since
+/// boot (`get_total_*`), since the previous refresh and per second between the last two
+/// refreshes (`get_*_per_second`).
+///
+/// It is returned by [`System::get_kernel_stats`][crate::System#method.get_kernel_stats].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+/// use std::{thread, time::Duration};
+///
+/// let mut s = System::new();
+/// thread::sleep(Duration::from_secs(1));
+/// s.refresh_cpu();
+/// let stats = s.get_kernel_stats();
+/// println!("{} forks since the last refresh", stats.get_forks());
+/// println!("{:.1} forks/s", stats.get_forks_per_second());
+/// println!("{} processes blocked on I/O", stats.get_blocked_processes());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct KernelStats {
+    refreshed: bool,
+    refresh_time: Option<Instant>,
+    elapsed: Duration,
+    context_switches: u64,
+    old_context_switches: u64,
+    interrupts: u64,
+    old_interrupts: u64,
+    softirqs: u64,
+    old_softirqs: u64,
+    forks: u64,
+    old_forks: u64,
+    running_processes: u64,
+    blocked_processes: u64,
+}
+
+impl KernelStats {
+    pub(crate) fn update(&mut self, data: &str, now: Instant) {
+        // A key missing from the file keeps its previous value.
+        let (mut context_switches, mut interrupts, mut softirqs, mut forks) = (
+            self.context_switches,
+            self.interrupts,
+            self.softirqs,
+            self.forks,
+        );
+        let first = !self.refreshed;
+        self.elapsed = match self.refresh_time {
+            Some(previous) => now.saturating_duration_since(previous),
+            None => Duration::default(),
+        };
+        self.refresh_time = Some(now);
+
+        for line in data.lines() {
+            // A line looks like this: "ctxt 1740962". The "intr" and "softirq" lines have the
+            // total first, followed by the count of each interrupt.
+            let mut parts = line.split_whitespace();
+            let (key, value) = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+                (Some(key), Some(value)) => (key, value),
+                _ => continue,
+            };
+            match key {
+                "ctxt" => context_switches = value,
+                "intr" => interrupts = value,
+                "softirq" => softirqs = value,
+                "processes" => forks = value,
+                "procs_running" => self.running_processes = value,
+                "procs_blocked" => self.blocked_processes = value,
+                _ => {}
+            }
+        }
+        old_and_new!(
+            self,
+            context_switches,
+            old_context_switches,
+            context_switches,
+            first
+        );
+        old_and_new!(self, interrupts, old_interrupts, interrupts, first);
+        old_and_new!(self, softirqs, old_softirqs, softirqs, first);
+        old_and_new!(self, forks, old_forks, forks, first);
+        self.refreshed = true;
+    }
+
+    /// Returns the number of context switches since the last refresh (`ctxt`).
+    pub fn get_context_switches(&self) -> u64 {
+        self.context_switches
+            .saturating_sub(self.old_context_switches)
+    }
+
+    /// Returns the number of context switches per second between the last two refreshes.
+    pub fn get_context_switches_per_second(&self) -> f64 {
+        self.per_second(self.get_context_switches())
+    }
+
+    /// Returns the number of context switches since boot (`ctxt`).
+    pub fn get_total_context_switches(&self) -> u64 {
+        self.context_switches
+    }
+
+    /// Returns the number of interrupts serviced since the last refresh (`intr`).
+    pub fn get_interrupts(&self) -> u64 {
+        self.interrupts.saturating_sub(self.old_interrupts)
+    }
+
+    /// Returns the number of interrupts serviced per second between the last two refreshes.
+    pub fn get_interrupts_per_second(&self) -> f64 {
+        self.per_second(self.get_interrupts())
+    }
+
+    /// Returns the number of interrupts serviced since boot (`intr`).
+    pub fn get_total_interrupts(&self) -> u64 {
+        self.interrupts
+    }
+
+    /// Returns the number of softirqs serviced since the last refresh (`softirq`).
+    pub fn get_softirqs(&self) -> u64 {
+        self.softirqs.saturating_sub(self.old_softirqs)
+    }
+
+    /// Returns the number of softirqs serviced per second between the last two refreshes.
+    pub fn get_softirqs_per_second(&self) -> f64 {
+        self.per_second(self.get_softirqs())
+    }
+
+    /// Returns the number of softirqs serviced since boot (`softirq`).
+    pub fn get_total_softirqs(&self) -> u64 {
+        self.softirqs
+    }
+
+    /// Returns the number of processes and threads created since the last refresh
+    /// (`processes`).
+    pub fn get_forks(&self) -> u64 {
+        self.forks.saturating_sub(self.old_forks)
+    }
+
+    /// Returns the number of processes and threads created per second between the last two refreshes.
+    pub fn get_forks_per_second(&self) -> f64 {
+        self.per_second(self.get_forks())
+    }
+
+    /// Returns the number of processes and threads created since boot (`processes`).
+    pub fn get_total_forks(&self) -> u64 {
+        self.forks
+    }
+
+    /// Returns the number of threads running or ready to run when the counters were refreshed
+    /// (`procs_running`).
+    pub fn get_running_processes(&self) -> u64 {
+        self.running_processes
+    }
+
+    /// Returns the number of threads blocked waiting for I/O when the counters were refreshed
+    /// (`procs_blocked`).
+    pub fn get_blocked_processes(&self) -> u64 {
+        self.blocked_processes
+    }
+
+    /// Returns the time elapsed between the last two refreshes.
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    fn per_second(&self, count: u64) -> f64 {
+        let elapsed = self.elapsed.as_secs_f64();
+        if elapsed > 0. {
+            count as f64 / elapsed
+        } else {
+            0.
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::KernelStats;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn check_kernel_stats_update() {
+        let mut stats = KernelStats::default();
+        let now = Instant::now();
+        stats.update(
+            "\
+intr 323123 0 0 12 0
+ctxt 1740962
+btime 1792333238
+processes 68823
+procs_running 3
+procs_blocked 1
+softirq 285573 0 102334 3 3933
+",
+            now,
+        );
+        assert_eq!(stats.get_total_interrupts(), 323123);
+        assert_eq!(stats.get_total_context_switches(), 1740962);
+        assert_eq!(stats.get_total_forks(), 68823);
+        assert_eq!(stats.get_total_softirqs(), 285573);
+        assert_eq!(stats.get_running_processes(), 3);
+        assert_eq!(stats.get_blocked_processes(), 1);
+        // The first refresh doesn't give any difference.
+        assert_eq!(stats.get_forks(), 0);
+        assert_eq!(stats.get_forks_per_second(), 0.);
+
+        stats.update(
+            "intr 323200 0\nctxt 1741000\nprocesses 68833\nprocs_running 1\n",
+            now + Duration::from_secs(2),
+        );
+        assert_eq!(stats.get_interrupts(), 77);
+        assert_eq!(stats.get_context_switches(), 38);
+        assert_eq!(stats.get_forks(), 10);
+        assert_eq!(stats.get_running_processes(), 1);
+        // "softirq" is missing so the previous value is kept.
+        assert_eq!(stats.get_softirqs(), 0);
+        assert_eq!(stats.get_total_softirqs(), 285573);
+        assert_eq!(stats.get_elapsed(), Duration::from_secs(2));
+        assert_eq!(stats.get_forks_per_second(), 5.);
+        assert_eq!(stats.get_context_switches_per_second(), 19.);
+    }
+}
@@ -0,0 +1,100 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::{LoadAvg, Pid};
+
+/// Load average with the scheduling information of `/proc/loadavg`, and the maximum pid.
+///
+/// It is returned by
+/// [`System::get_detailed_load_average`][crate::System#method.get_detailed_load_average].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// let load = s.get_detailed_load_average();
+/// println!("{} runnable / {} tasks", load.runnable, load.total);
+/// if load.pid_usage() > 90. {
+///     println!("running out of pids!");
+/// }
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct DetailedLoadAvg {
+    /// Load averages, as returned by
+    /// [`SystemExt::get_load_average`][crate::SystemExt::get_load_average].
+    pub load: LoadAvg,
+    /// Number of scheduling entities (threads) currently runnable.
+    pub runnable: u64,
+    /// Number of scheduling entities (threads) on the system.
+    pub total: u64,
+    /// Last pid allocated by the kernel.
+    pub last_pid: Pid,
+    /// Maximum pid value plus one (`/proc/sys/kernel/pid_max`), `0` if it cannot be read.
+    pub pid_max: Pid,
+}
+
+impl DetailedLoadAvg {
+    /// Returns the share (in %) of the pids in use: each thread uses a pid, so it is the
+    /// number of scheduling entities divided by `pid_max`. `0` is returned if `pid_max` is
+    /// unknown.
+    pub fn pid_usage(&self) -> f32 {
+        if self.pid_max <= 0 {
+            0.
+        } else {
+            self.total as f32 * 100. / self.pid_max as f32
+        }
+    }
+}
+
+/// Parses the load averages, the first three values of `/proc/loadavg`.
+pub(crate) fn parse_load(data: &str) -> Option<LoadAvg> {
+    // The content looks like this: "0.85 0.50 0.38 2/74 13624".
+    let mut parts = data.split_whitespace();
+    Some(LoadAvg {
+        one: parts.next()?.parse().ok()?,
+        five: parts.next()?.parse().ok()?,
+        fifteen: parts.next()?.parse().ok()?,
+    })
+}
+
+pub(crate) fn parse_loadavg(data: &str, pid_max: Pid) -> Option<DetailedLoadAvg> {
+    let load = parse_load(data)?;
+    let mut parts = data.split_whitespace().skip(3);
+    let mut entities = parts.next()?.splitn(2, '/');
+    Some(DetailedLoadAvg {
+        load,
+        runnable: entities.next()?.parse().ok()?,
+        total: entities.next()?.parse().ok()?,
+        last_pid: parts.next()?.parse().ok()?,
+        pid_max,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_load, parse_loadavg};
+
+    #[test]
+    fn check_parse_loadavg() {
+        let load = parse_loadavg("0.85 0.50 0.38 2/74 13624\n", 32768).expect("failed to parse");
+        assert_eq!(load.load.one, 0.85);
+        assert_eq!(load.load.five, 0.50);
+        assert_eq!(load.load.fifteen, 0.38);
+        assert_eq!(load.runnable, 2);
+        assert_eq!(load.total, 74);
+        assert_eq!(load.last_pid, 13624);
+        assert_eq!(load.pid_max, 32768);
+        assert!((load.pid_usage() - 74. * 100. / 32768.).abs() < 0.0001);
+
+        assert_eq!(
+            parse_loadavg("0.85 0.50 0.38 2/74 13624", 0).map(|l| l.pid_usage()),
+            Some(0.)
+        );
+        assert!(parse_loadavg("0.85 0.50", 32768).is_none());
+        assert!(parse_loadavg("0.85 0.50 0.38", 32768).is_none());
+        assert_eq!(parse_load("0.85 0.50 0.38").map(|l| l.fifteen), Some(0.38));
+    }
+}
@@ -0,0 +1,26 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+/// Moves the current value of `$name` into `$old` and stores the new one in `$name`.
+///
+/// The five arguments form takes a `$first` boolean: when it's `true`, `$old` is set to the new
+/// value too so the difference between the two is `0` after the first refresh.
+macro_rules! old_and_new {
+    ($ty_:expr, $name:ident, $old:ident) => {{
+        $ty_.$old = $ty_.$name;
+        $ty_.$name = $name;
+    }};
+    ($ty_:expr, $name:ident, $old:ident, $path:expr) => {{
+        let _tmp = $path;
+        $ty_.$old = $ty_.$name;
+        $ty_.$name = _tmp;
+    }};
+    ($ty_:expr, $name:ident, $old:ident, $value:expr, $first:expr) => {{
+        let _tmp = $value;
+        $ty_.$old = if $first { _tmp } else { $ty_.$name };
+        $ty_.$name = _tmp;
+    }};
+}
@@ -0,0 +1,259 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use std::collections::HashMap;
+
+/// Memory information, read from `/proc/meminfo`.
+///
+/// All the values are in kB, except for the `huge_pages_*` ones which are a number of pages. The
+/// fields which aren't reported by the running kernel are set to `0`. The entries which don't
+/// have a field (because they were added by a recent kernel for example) are stored in `other`.
+///
+/// It is returned by [`System::get_memory_info`][crate::System#method.get_memory_info].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// let info = s.get_memory_info();
+/// println!("committed: {} / {} kB", info.committed, info.commit_limit);
+/// println!("dirty: {} kB", info.dirty);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryInfo {
+    /// Total usable memory (`MemTotal`).
+    pub total: u64,
+    /// Memory not used at all (`MemFree`).
+    pub free: u64,
+    /// Estimation of the memory available to start new applications without swapping
+    /// (`MemAvailable`, Linux 3.14 and later).
+    pub available: u64,
+    /// Memory used by block device buffers (`Buffers`).
+    pub buffers: u64,
+    /// Memory used by the page cache (files read from the disk) and by tmpfs (`Cached`).
+    pub cached: u64,
+    /// Memory which was swapped out and is back in memory but still in the swap (`SwapCached`).
+    pub swap_cached: u64,
+    /// Memory used recently, usually not reclaimed unless necessary (`Active`).
+    pub active: u64,
+    /// Memory not used recently, candidate for reclaim (`Inactive`).
+    pub inactive: u64,
+    /// Active anonymous memory (`Active(anon)`).
+    pub active_anon: u64,
+    /// Inactive anonymous memory (`Inactive(anon)`).
+    pub inactive_anon: u64,
+    /// Active file-backed memory (`Active(file)`).
+    pub active_file: u64,
+    /// Inactive file-backed memory (`Inactive(file)`).
+    pub inactive_file: u64,
+    /// Memory which cannot be reclaimed (locked pages, ramfs, etc) (`Unevictable`).
+    pub unevictable: u64,
+    /// Memory locked with `mlock` (`Mlocked`).
+    pub mlocked: u64,
+    /// Total swap space (`SwapTotal`).
+    pub swap_total: u64,
+    /// Unused swap space (`SwapFree`).
+    pub swap_free: u64,
+    /// Memory used by the zswap backend (`Zswap`).
+    pub zswap: u64,
+    /// Amount of anonymous memory stored in zswap (uncompressed size) (`Zswapped`).
+    pub zswapped: u64,
+    /// Memory waiting to be written back to the disk (`Dirty`).
+    pub dirty: u64,
+    /// Memory being written back to the disk (`Writeback`).
+    pub writeback: u64,
+    /// Non file-backed pages mapped into user-space page tables (`AnonPages`).
+    pub anon_pages: u64,
+    /// Files mapped into memory (such as libraries) (`Mapped`).
+    pub mapped: u64,
+    /// Memory used by shared memory and tmpfs (`Shmem`).
+    pub shmem: u64,
+    /// Kernel allocations which can be reclaimed under memory pressure (`KReclaimable`).
+    pub kernel_reclaimable: u64,
+    /// Memory used by the kernel slab allocator (`Slab`).
+    pub slab: u64,
+    /// Part of the slab memory which can be reclaimed (such as caches) (`SReclaimable`).
+    pub slab_reclaimable: u64,
+    /// Part of the slab memory which cannot be reclaimed (`SUnreclaim`).
+    pub slab_unreclaimable: u64,
+    /// Memory used by the kernel stacks of all tasks (`KernelStack`).
+    pub kernel_stack: u64,
+    /// Memory used by the page tables (`PageTables`).
+    pub page_tables: u64,
+    /// Memory used by secondary page tables (KVM, IOMMU) (`SecPageTables`).
+    pub secondary_page_tables: u64,
+    /// NFS pages sent to the server but not yet committed (`NFS_Unstable`).
+    pub nfs_unstable: u64,
+    /// Memory used for block device bounce buffers (`Bounce`).
+    pub bounce: u64,
+    /// Memory used by FUSE for temporary writeback buffers (`WritebackTmp`).
+    pub writeback_tmp: u64,
+    /// Total amount of memory which can be allocated, based on the overcommit ratio
+    /// (`CommitLimit`).
+    pub commit_limit: u64,
+    /// Amount of memory currently allocated, including memory not used yet (`Committed_AS`).
+    pub committed: u64,
+    /// Total size of the vmalloc memory area (`VmallocTotal`).
+    pub vmalloc_total: u64,
+    /// Used part of the vmalloc memory area (`VmallocUsed`).
+    pub vmalloc_used: u64,
+    /// Largest contiguous free block of the vmalloc memory area (`VmallocChunk`).
+    pub vmalloc_chunk: u64,
+    /// Memory used by the percpu allocator (`Percpu`).
+    pub percpu: u64,
+    /// Memory the kernel identified as corrupted (`HardwareCorrupted`).
+    pub hardware_corrupted: u64,
+    /// Anonymous memory backed by transparent huge pages (`AnonHugePages`).
+    pub anon_huge_pages: u64,
+    /// Shared memory and tmpfs backed by huge pages (`ShmemHugePages`).
+    pub shmem_huge_pages: u64,
+    /// Shared memory mapped into user-space with huge pages (`ShmemPmdMapped`).
+    pub shmem_pmd_mapped: u64,
+    /// Page cache backed by huge pages (`FileHugePages`).
+    pub file_huge_pages: u64,
+    /// Page cache mapped into user-space with huge pages (`FilePmdMapped`).
+    pub file_pmd_mapped: u64,
+    /// Total memory reserved for the Contiguous Memory Allocator (`CmaTotal`).
+    pub cma_total: u64,
+    /// Free memory in the Contiguous Memory Allocator reserves (`CmaFree`).
+    pub cma_free: u64,
+    /// Number of huge pages in the pool (`HugePages_Total`).
+    pub huge_pages_total: u64,
+    /// Number of huge pages not allocated yet (`HugePages_Free`).
+    pub huge_pages_free: u64,
+    /// Number of huge pages reserved but not allocated yet (`HugePages_Rsvd`).
+    pub huge_pages_reserved: u64,
+    /// Number of huge pages above the pool size (`HugePages_Surp`).
+    pub huge_pages_surplus: u64,
+    /// Default size of the huge pages (`Hugepagesize`).
+    pub huge_page_size: u64,
+    /// Memory used by huge pages of all sizes (`Hugetlb`).
+    pub hugetlb: u64,
+    /// Memory mapped by the kernel with 4 kB pages (`DirectMap4k`).
+    pub direct_map_4k: u64,
+    /// Memory mapped by the kernel with 2 MB pages (`DirectMap2M`).
+    pub direct_map_2m: u64,
+    /// Memory mapped by the kernel with 1 GB pages (`DirectMap1G`).
+    pub direct_map_1g: u64,
+    /// Entries which don't have a field in this struct, indexed by name.
+    pub other: HashMap<String, u64>,
+}
+
+pub(crate) fn parse_meminfo(data: &str) -> MemoryInfo {
+    let mut info = MemoryInfo::default();
+
+    for line in data.lines() {
+        // A line looks like this: "MemTotal:        6158152 kB".
+        let mut parts = line.splitn(2, ':');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key, value.trim()),
+            _ => continue,
+        };
+        let mut value_parts = value.split_whitespace();
+        let mut value = match value_parts.next().and_then(|v| v.parse::<u64>().ok()) {
+            Some(value) => value,
+            None => continue,
+        };
+        if value_parts.next() == Some("kB") {
+            // /proc/meminfo reports KiB, though it says "kB". Convert it.
+            value = value * 128 / 125;
+        }
+        let field = match key {
+            "MemTotal" => &mut info.total,
+            "MemFree" => &mut info.free,
+            "MemAvailable" => &mut info.available,
+            "Buffers" => &mut info.buffers,
+            "Cached" => &mut info.cached,
+            "SwapCached" => &mut info.swap_cached,
+            "Active" => &mut info.active,
+            "Inactive" => &mut info.inactive,
+            "Active(anon)" => &mut info.active_anon,
+            "Inactive(anon)" => &mut info.inactive_anon,
+            "Active(file)" => &mut info.active_file,
+            "Inactive(file)" => &mut info.inactive_file,
+            "Unevictable" => &mut info.unevictable,
+            "Mlocked" => &mut info.mlocked,
+            "SwapTotal" => &mut info.swap_total,
+            "SwapFree" => &mut info.swap_free,
+            "Zswap" => &mut info.zswap,
+            "Zswapped" => &mut info.zswapped,
+            "Dirty" => &mut info.dirty,
+            "Writeback" => &mut info.writeback,
+            "AnonPages" => &mut info.anon_pages,
+            "Mapped" => &mut info.mapped,
+            "Shmem" => &mut info.shmem,
+            "KReclaimable" => &mut info.kernel_reclaimable,
+            "Slab" => &mut info.slab,
+            "SReclaimable" => &mut info.slab_reclaimable,
+            "SUnreclaim" => &mut info.slab_unreclaimable,
+            "KernelStack" => &mut info.kernel_stack,
+            "PageTables" => &mut info.page_tables,
+            "SecPageTables" => &mut info.secondary_page_tables,
+            "NFS_Unstable" => &mut info.nfs_unstable,
+            "Bounce" => &mut info.bounce,
+            "WritebackTmp" => &mut info.writeback_tmp,
+            "CommitLimit" => &mut info.commit_limit,
+            "Committed_AS" => &mut info.committed,
+            "VmallocTotal" => &mut info.vmalloc_total,
+            "VmallocUsed" => &mut info.vmalloc_used,
+            "VmallocChunk" => &mut info.vmalloc_chunk,
+            "Percpu" => &mut info.percpu,
+            "HardwareCorrupted" => &mut info.hardware_corrupted,
+            "AnonHugePages" => &mut info.anon_huge_pages,
+            "ShmemHugePages" => &mut info.shmem_huge_pages,
+            "ShmemPmdMapped" => &mut info.shmem_pmd_mapped,
+            "FileHugePages" => &mut info.file_huge_pages,
+            "FilePmdMapped" => &mut info.file_pmd_mapped,
+            "CmaTotal" => &mut info.cma_total,
+            "CmaFree" => &mut info.cma_free,
+            "HugePages_Total" => &mut info.huge_pages_total,
+            "HugePages_Free" => &mut info.huge_pages_free,
+            "HugePages_Rsvd" => &mut info.huge_pages_reserved,
+            "HugePages_Surp" => &mut info.huge_pages_surplus,
+            "Hugepagesize" => &mut info.huge_page_size,
+            "Hugetlb" => &mut info.hugetlb,
+            "DirectMap4k" => &mut info.direct_map_4k,
+            "DirectMap2M" => &mut info.direct_map_2m,
+            "DirectMap1G" => &mut info.direct_map_1g,
+            _ => {
+                info.other.insert(key.to_owned(), value);
+                continue;
+            }
+        };
+        *field = value;
+    }
+    info
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_meminfo;
+
+    #[test]
+    fn check_parse_meminfo() {
+        let info = parse_meminfo(
+            "\
+MemTotal:        6158152 kB
+MemFree:         3925708 kB
+Active(anon):         20 kB
+Dirty:              1000 kB
+Committed_AS:     338504 kB
+HugePages_Total:       4
+DirectMap4M:        8000 kB
+Invalid line
+",
+        );
+        assert_eq!(info.total, 6158152 * 128 / 125);
+        assert_eq!(info.free, 3925708 * 128 / 125);
+        assert_eq!(info.active_anon, 20 * 128 / 125);
+        assert_eq!(info.dirty, 1024);
+        assert_eq!(info.committed, 338504 * 128 / 125);
+        assert_eq!(info.huge_pages_total, 4);
+        assert_eq!(info.available, 0);
+        assert_eq!(info.other.len(), 1);
+        assert_eq!(info.other.get("DirectMap4M"), Some(&8192));
+    }
+}
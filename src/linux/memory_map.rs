@@ -0,0 +1,190 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use std::path::Path;
+
+/// A memory mapping of a process, as listed in `/proc/[pid]/maps`.
+///
+/// It is returned by [`Process::memory_maps`][crate::Process#method.memory_maps].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// if let Some(process) = s.get_process(1337) {
+///     for map in process.memory_maps() {
+///         println!("{:x}-{:x} {}", map.start_address, map.end_address, map.pathname);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryMap {
+    /// Start address of the mapping.
+    pub start_address: u64,
+    /// End address (excluded) of the mapping.
+    pub end_address: u64,
+    /// The mapping can be read.
+    pub readable: bool,
+    /// The mapping can be written.
+    pub writable: bool,
+    /// The mapping can be executed.
+    pub executable: bool,
+    /// The mapping is shared (`false` means it is private, copy on write).
+    pub shared: bool,
+    /// Offset into the mapped file.
+    pub offset: u64,
+    /// Major number of the device holding the mapped file.
+    pub device_major: u32,
+    /// Minor number of the device holding the mapped file.
+    pub device_minor: u32,
+    /// Inode of the mapped file on its device (`0` for anonymous mappings).
+    pub inode: u64,
+    /// Path of the mapped file, pseudo-path (such as `[heap]` or `[stack]`) or empty string for
+    /// anonymous mappings. The " (deleted)" suffix is removed, look at `deleted` instead.
+    pub pathname: String,
+    /// The mapped file has been deleted (or replaced) since it was mapped.
+    pub deleted: bool,
+}
+
+impl MemoryMap {
+    /// Returns the path of the mapped file, or `None` if this mapping isn't backed by a file.
+    pub fn path(&self) -> Option<&Path> {
+        if self.pathname.starts_with('/') {
+            Some(Path::new(&self.pathname))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the size of the mapping (in bytes).
+    pub fn size(&self) -> u64 {
+        self.end_address.saturating_sub(self.start_address)
+    }
+}
+
+fn parse_line(line: &str) -> Option<MemoryMap> {
+    // A line looks like this (the pathname can contain spaces):
+    //
+    // 7f2b1c1e5000-7f2b1c20b000 r-xp 00026000 fe:00 1054  /usr/lib/libc.so.6
+    let mut parts = line.splitn(6, ' ');
+    let mut addresses = parts.next()?.splitn(2, '-');
+    let start_address = u64::from_str_radix(addresses.next()?, 16).ok()?;
+    let end_address = u64::from_str_radix(addresses.next()?, 16).ok()?;
+    let permissions = parts.next()?.as_bytes();
+    if permissions.len() < 4 {
+        return None;
+    }
+    let offset = u64::from_str_radix(parts.next()?, 16).ok()?;
+    let mut device = parts.next()?.splitn(2, ':');
+    let device_major = u32::from_str_radix(device.next()?, 16).ok()?;
+    let device_minor = u32::from_str_radix(device.next()?, 16).ok()?;
+    let inode = parts.next()?.parse().ok()?;
+    let pathname = parts.next().unwrap_or("").trim_start();
+    let (pathname, deleted) = match pathname.strip_suffix(" (deleted)") {
+        Some(pathname) => (pathname, true),
+        None => (pathname, false),
+    };
+
+    Some(MemoryMap {
+        start_address,
+        end_address,
+        readable: permissions[0] == b'r',
+        writable: permissions[1] == b'w',
+        executable: permissions[2] == b'x',
+        shared: permissions[3] == b's',
+        offset,
+        device_major,
+        device_minor,
+        inode,
+        pathname: pathname.to_owned(),
+        deleted,
+    })
+}
+
+pub(crate) fn parse_memory_maps(data: &str) -> Vec<MemoryMap> {
+    data.lines().filter_map(parse_line).collect()
+}
+
+pub(crate) fn get_memory_maps(path: &Path) -> Vec<MemoryMap> {
+    match super::system::get_all_data(path.join("maps"), 16_384) {
+        Ok(data) => parse_memory_maps(&data),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn is_shared_library(path: &Path) -> bool {
+    match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) => name.ends_with(".so") || name.contains(".so."),
+        None => false,
+    }
+}
+
+pub(crate) fn get_loaded_libraries(maps: Vec<MemoryMap>) -> Vec<MemoryMap> {
+    let mut libraries: Vec<MemoryMap> = Vec::new();
+
+    for map in maps {
+        if !map.path().map(is_shared_library).unwrap_or(false) {
+            continue;
+        }
+        if let Some(library) = libraries.iter_mut().find(|l| l.pathname == map.pathname) {
+            // If any of the library's mappings refers to a deleted file, the library is
+            // considered as deleted.
+            library.deleted |= map.deleted;
+        } else {
+            libraries.push(map);
+        }
+    }
+    libraries
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_loaded_libraries, parse_memory_maps};
+
+    #[test]
+    fn check_parse_memory_maps() {
+        let maps = parse_memory_maps(
+            "\
+55d0c5a3e000-55d0c5a40000 r--p 00000000 fd:01 1311                       /usr/bin/my app
+55d0c6b8e000-55d0c6baf000 rw-p 00000000 00:00 0                          [heap]
+7f2b1c1bf000-7f2b1c1e5000 r--p 00000000 fd:01 1054                       /usr/lib/libc.so.6
+7f2b1c1e5000-7f2b1c33a000 r-xp 00026000 fd:01 1054                       /usr/lib/libc.so.6
+7f2b1c400000-7f2b1c460000 r-xp 00000000 fd:01 2048                       /usr/lib/libssl.so.1.1 (deleted)
+7f2b1c500000-7f2b1c501000 rw-s 00000000 00:05 77
+",
+        );
+        assert_eq!(maps.len(), 6);
+
+        assert_eq!(maps[0].start_address, 0x55d0c5a3e000);
+        assert_eq!(maps[0].end_address, 0x55d0c5a40000);
+        assert_eq!(maps[0].size(), 0x2000);
+        assert!(maps[0].readable && !maps[0].writable && !maps[0].executable);
+        assert!(!maps[0].shared);
+        assert_eq!(maps[0].device_major, 0xfd);
+        assert_eq!(maps[0].device_minor, 1);
+        assert_eq!(maps[0].inode, 1311);
+        assert_eq!(maps[0].pathname, "/usr/bin/my app");
+
+        assert_eq!(maps[1].pathname, "[heap]");
+        assert!(maps[1].path().is_none());
+
+        assert_eq!(maps[3].offset, 0x26000);
+        assert!(maps[3].executable);
+
+        assert_eq!(maps[4].pathname, "/usr/lib/libssl.so.1.1");
+        assert!(maps[4].deleted);
+
+        assert!(maps[5].shared);
+        assert!(maps[5].pathname.is_empty());
+
+        let libraries = get_loaded_libraries(maps);
+        assert_eq!(libraries.len(), 2);
+        assert_eq!(libraries[0].pathname, "/usr/lib/libc.so.6");
+        assert!(!libraries[0].deleted);
+        assert_eq!(libraries[1].pathname, "/usr/lib/libssl.so.1.1");
+        assert!(libraries[1].deleted);
+    }
+}
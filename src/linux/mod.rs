@@ -4,17 +4,53 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+#[macro_use]
+mod macros;
+
+pub mod cgroup;
+pub mod command;
 pub mod component;
+pub mod current;
 pub mod disk;
+pub mod executable;
+pub mod interrupts;
+pub mod kernel_stats;
+pub mod load_avg;
+pub mod memory;
+pub mod memory_map;
 pub mod network;
+pub mod numa;
+pub mod pressure;
 pub mod process;
 pub mod processor;
+pub mod swap;
 pub mod system;
+pub mod thread;
 pub mod users;
+pub mod vmstat;
 
+pub use self::cgroup::CgroupLimits;
+pub use self::command::{monitor_command, CommandReport, CommandSample, ResourceUsage};
 pub use self::component::Component;
+pub use self::current::{current_process, CurrentProcess};
 pub use self::disk::Disk;
+pub use self::executable::{ElfClass, ExecutableInfo};
+pub use self::interrupts::Interrupt;
+pub use self::kernel_stats::KernelStats;
+pub use self::load_avg::DetailedLoadAvg;
+pub use self::memory::MemoryInfo;
+pub use self::memory_map::MemoryMap;
 pub use self::network::{NetworkData, Networks};
-pub use self::process::{Process, ProcessStatus};
+pub use self::numa::{NumaNode, NumaStats};
+pub use self::pressure::{
+    Pressure, PressureResource, PressureStallKind, PressureTrigger, PressureValues,
+};
+pub use self::process::{
+    realtime_signal, IoPriority, IoPriorityClass, Process, ProcessMemoryDetails, ProcessStatus,
+    SchedulerStats,
+};
 pub use self::processor::Processor;
+pub use self::swap::{Swap, SwapType, ZramStats};
 pub use self::system::System;
+pub use self::thread::Thread;
+pub use self::vmstat::VmStat;
@@ -23,18 +23,6 @@ pub struct Networks {
     interfaces: HashMap<String, NetworkData>,
 }
 
-macro_rules! old_and_new {
-    ($ty_:expr, $name:ident, $old:ident) => {{
-        $ty_.$old = $ty_.$name;
-        $ty_.$name = $name;
-    }};
-    ($ty_:expr, $name:ident, $old:ident, $path:expr) => {{
-        let _tmp = $path;
-        $ty_.$old = $ty_.$name;
-        $ty_.$name = _tmp;
-    }};
-}
-
 fn read<P: AsRef<Path>>(parent: P, path: &str, data: &mut Vec<u8>) -> u64 {
     if let Ok(mut f) = File::open(parent.as_ref().join(path)) {
         if let Ok(size) = f.read(data) {
@@ -0,0 +1,245 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::memory::{parse_meminfo, MemoryInfo};
+use crate::sys::system::get_all_data;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Allocation counters of a NUMA node, read from its `numastat` file. They are numbers of pages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NumaStats {
+    /// Pages successfully allocated on this node as intended (`numa_hit`).
+    pub hit: u64,
+    /// Pages allocated on this node while they were intended for another one because it was
+    /// out of memory (`numa_miss`).
+    pub miss: u64,
+    /// Pages intended for this node but allocated on another one (`numa_foreign`).
+    pub foreign: u64,
+    /// Interleaved pages successfully allocated on this node (`interleave_hit`).
+    pub interleave_hit: u64,
+    /// Pages allocated on this node while the process was running on it (`local_node`).
+    pub local_node: u64,
+    /// Pages allocated on this node while the process was running on another one
+    /// (`other_node`).
+    pub other_node: u64,
+}
+
+/// A NUMA node, read from `/sys/devices/system/node/node*`.
+///
+/// The nodes are returned by [`System::get_numa_nodes`][crate::System#method.get_numa_nodes].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// for node in s.get_numa_nodes() {
+///     println!(
+///         "node {}: CPUs {:?}, {} kB free, {} remote allocations",
+///         node.id, node.cpus, node.memory.free, node.stats.other_node,
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaNode {
+    /// Identifier of the node.
+    pub id: u32,
+    /// Identifiers of the CPUs of the node (`cpu0` has the identifier `0`). Use
+    /// [`System::get_numa_node_processors`][crate::System#method.get_numa_node_processors] to
+    /// get the corresponding [`Processor`][crate::Processor]s.
+    pub cpus: Vec<usize>,
+    /// Relative distance from this node to each node, indexed by node position (`10` is the
+    /// distance of a node to itself).
+    pub distances: Vec<u32>,
+    /// Memory information of the node. Only a subset of the fields of `/proc/meminfo` is
+    /// reported by the nodes, the other ones are `0`.
+    pub memory: MemoryInfo,
+    /// Allocation counters of the node.
+    pub stats: NumaStats,
+}
+
+/// Parses a list of CPUs or nodes such as "0-3,8,10-11".
+pub(crate) fn parse_cpu_list(data: &str) -> Vec<usize> {
+    let mut list = Vec::new();
+
+    for range in data.trim().split(',').filter(|r| !r.is_empty()) {
+        let mut bounds = range.splitn(2, '-');
+        let start = bounds.next().and_then(|v| v.parse::<usize>().ok());
+        let end = match bounds.next() {
+            Some(end) => end.parse::<usize>().ok(),
+            None => start,
+        };
+        if let (Some(start), Some(end)) = (start, end) {
+            list.extend(start..=end);
+        }
+    }
+    list
+}
+
+pub(crate) fn parse_numastat(data: &str) -> NumaStats {
+    let mut stats = NumaStats::default();
+
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let value = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+            (Some(key), Some(value)) => (key, value),
+            _ => continue,
+        };
+        match value {
+            ("numa_hit", v) => stats.hit = v,
+            ("numa_miss", v) => stats.miss = v,
+            ("numa_foreign", v) => stats.foreign = v,
+            ("interleave_hit", v) => stats.interleave_hit = v,
+            ("local_node", v) => stats.local_node = v,
+            ("other_node", v) => stats.other_node = v,
+            _ => {}
+        }
+    }
+    stats
+}
+
+// Each line of a node `meminfo` file starts with "Node [id] ", the rest is the same as
+// `/proc/meminfo`.
+fn parse_node_meminfo(data: &str) -> MemoryInfo {
+    let data = data
+        .lines()
+        .filter_map(|line| line.splitn(3, ' ').nth(2))
+        .collect::<Vec<_>>()
+        .join("\n");
+    parse_meminfo(&data)
+}
+
+fn get_numa_node(path: &Path, id: u32) -> NumaNode {
+    let read = |file: &str| get_all_data(path.join(file), 4096).unwrap_or_default();
+
+    NumaNode {
+        id,
+        cpus: parse_cpu_list(&read("cpulist")),
+        distances: read("distance")
+            .split_whitespace()
+            .filter_map(|d| d.parse().ok())
+            .collect(),
+        memory: parse_node_meminfo(&read("meminfo")),
+        stats: parse_numastat(&read("numastat")),
+    }
+}
+
+pub(crate) fn get_numa_nodes() -> Vec<NumaNode> {
+    let mut nodes = match fs::read_dir("/sys/devices/system/node") {
+        Ok(dir) => dir
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("node")?
+                    .parse()
+                    .ok()?;
+                Some(get_numa_node(&entry.path(), id))
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    nodes.sort_unstable_by_key(|node| node.id);
+    nodes
+}
+
+/// Returns the memory (in kB) used on each NUMA node by the mappings of `/proc/[pid]/numa_maps`.
+pub(crate) fn parse_numa_maps(data: &str) -> HashMap<u32, u64> {
+    let mut memory = HashMap::new();
+
+    // A line looks like this:
+    // "55b706f67000 default file=/usr/bin/cat anon=1 dirty=1 N0=1 N1=3 kernelpagesize_kB=4"
+    for line in data.lines() {
+        let mut page_size = 4;
+        let mut pages = Vec::new();
+
+        for item in line.split_whitespace() {
+            let mut parts = item.splitn(2, '=');
+            match (
+                parts.next(),
+                parts.next().and_then(|v| v.parse::<u64>().ok()),
+            ) {
+                (Some("kernelpagesize_kB"), Some(size)) => page_size = size,
+                (Some(key), Some(nb_pages)) if key.starts_with('N') => {
+                    if let Ok(node) = key[1..].parse::<u32>() {
+                        pages.push((node, nb_pages));
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (node, nb_pages) in pages {
+            *memory.entry(node).or_insert(0) += nb_pages * page_size;
+        }
+    }
+    // Like for `/proc/meminfo`, the page size is in KiB. We convert it into kB.
+    for value in memory.values_mut() {
+        *value = *value * 128 / 125;
+    }
+    memory
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cpu_list, parse_node_meminfo, parse_numa_maps, parse_numastat};
+
+    #[test]
+    fn check_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("\n").is_empty());
+    }
+
+    #[test]
+    fn check_parse_numa_node() {
+        let memory = parse_node_meminfo(
+            "\
+Node 1 MemTotal:        5603064 kB
+Node 1 MemFree:         3357780 kB
+Node 1 MemUsed:         2245284 kB
+Node 1 HugePages_Total:     0
+",
+        );
+        assert_eq!(memory.total, 5603064 * 128 / 125);
+        assert_eq!(memory.free, 3357780 * 128 / 125);
+        assert_eq!(memory.other.get("MemUsed"), Some(&(2245284 * 128 / 125)));
+
+        let stats = parse_numastat(
+            "\
+numa_hit 33514808
+numa_miss 12
+numa_foreign 3
+interleave_hit 1024
+local_node 33514800
+other_node 8
+",
+        );
+        assert_eq!(stats.hit, 33514808);
+        assert_eq!(stats.miss, 12);
+        assert_eq!(stats.foreign, 3);
+        assert_eq!(stats.interleave_hit, 1024);
+        assert_eq!(stats.local_node, 33514800);
+        assert_eq!(stats.other_node, 8);
+    }
+
+    #[test]
+    fn check_parse_numa_maps() {
+        let memory = parse_numa_maps(
+            "\
+55b706f5d000 default file=/usr/bin/cat mapped=2 N0=2 kernelpagesize_kB=4
+7f0000000000 bind:1 anon=3 dirty=3 N1=3 kernelpagesize_kB=2048
+7f1000000000 interleave:0-1 anon=4 N0=2 N1=2 kernelpagesize_kB=4
+7f2000000000 default
+",
+        );
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory.get(&0), Some(&(4 * 4 * 128 / 125)));
+        assert_eq!(memory.get(&1), Some(&((3 * 2048 + 2 * 4) * 128 / 125)));
+    }
+}
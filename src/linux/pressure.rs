@@ -0,0 +1,272 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::cgroup::get_cgroup_v2_path;
+use crate::sys::system::get_all_data;
+
+use libc::{c_int, poll, pollfd, POLLERR, POLLPRI};
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Resource tracked by the Pressure Stall Information (PSI) of the kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureResource {
+    /// CPU pressure (`cpu`).
+    Cpu,
+    /// Memory pressure (`memory`).
+    Memory,
+    /// I/O pressure (`io`).
+    Io,
+    /// Interrupts pressure (`irq`, Linux 6.1 and later).
+    Irq,
+}
+
+impl PressureResource {
+    fn file_name(self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+            PressureResource::Irq => "irq",
+        }
+    }
+}
+
+/// Which tasks are considered by a [`PressureTrigger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureStallKind {
+    /// At least some tasks are stalled on the resource.
+    Some,
+    /// All non-idle tasks are stalled on the resource simultaneously.
+    Full,
+}
+
+/// Pressure values of a line (`some` or `full`) of a PSI file.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PressureValues {
+    /// Share of time (in %) tasks were stalled over the last 10 seconds.
+    pub avg10: f32,
+    /// Share of time (in %) tasks were stalled over the last 60 seconds.
+    pub avg60: f32,
+    /// Share of time (in %) tasks were stalled over the last 300 seconds.
+    pub avg300: f32,
+    /// Total stall time (in microseconds).
+    pub total: u64,
+}
+
+/// Pressure Stall Information of a resource.
+///
+/// It is returned by [`System::get_pressure`][crate::System#method.get_pressure] and
+/// [`System::get_cgroup_pressure`][crate::System#method.get_cgroup_pressure].
+///
+/// ```no_run
+/// use sysinfo::{PressureResource, System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(pressure) = s.get_pressure(PressureResource::Memory) {
+///     if let Some(some) = pressure.some {
+///         println!("memory pressure: {}% over the last 10 seconds", some.avg10);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Pressure {
+    /// Time during which at least some tasks were stalled. It isn't reported for interrupts.
+    pub some: Option<PressureValues>,
+    /// Time during which all non-idle tasks were stalled simultaneously. It isn't reported for
+    /// the CPU before Linux 5.13 (and is always `0` for the system-wide CPU pressure).
+    pub full: Option<PressureValues>,
+}
+
+fn parse_values(data: &str) -> Option<PressureValues> {
+    let mut values = PressureValues::default();
+
+    for item in data.split_whitespace() {
+        let mut parts = item.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some("avg10"), Some(v)) => values.avg10 = v.parse().ok()?,
+            (Some("avg60"), Some(v)) => values.avg60 = v.parse().ok()?,
+            (Some("avg300"), Some(v)) => values.avg300 = v.parse().ok()?,
+            (Some("total"), Some(v)) => values.total = v.parse().ok()?,
+            _ => {}
+        }
+    }
+    Some(values)
+}
+
+pub(crate) fn parse_pressure(data: &str) -> Pressure {
+    let mut pressure = Pressure::default();
+
+    // Each line looks like this: "some avg10=0.65 avg60=0.33 avg300=0.26 total=8457524".
+    for line in data.lines() {
+        let mut parts = line.splitn(2, ' ');
+        match (parts.next(), parts.next()) {
+            (Some("some"), Some(values)) => pressure.some = parse_values(values),
+            (Some("full"), Some(values)) => pressure.full = parse_values(values),
+            _ => {}
+        }
+    }
+    pressure
+}
+
+fn get_pressure_path(cgroup: Option<&str>, resource: PressureResource) -> Option<PathBuf> {
+    match cgroup {
+        Some(cgroup) => {
+            Some(get_cgroup_v2_path(cgroup)?.join(format!("{}.pressure", resource.file_name())))
+        }
+        None => Some(Path::new("/proc/pressure").join(resource.file_name())),
+    }
+}
+
+pub(crate) fn get_pressure(cgroup: Option<&str>, resource: PressureResource) -> Option<Pressure> {
+    let data = get_all_data(get_pressure_path(cgroup, resource)?, 1024).ok()?;
+    Some(parse_pressure(&data))
+}
+
+/// A PSI trigger, used to wait until a resource is under pressure.
+///
+/// The trigger fires when tasks are stalled for more than `stall` during a time `window`. See
+/// the kernel PSI documentation for the limits of these values (the window must be between
+/// 500ms and 10s and, for unprivileged users, be a multiple of 2s).
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use sysinfo::{PressureResource, PressureStallKind, PressureTrigger};
+///
+/// // Triggers when tasks are stalled on memory for more than 150ms in a 1s window.
+/// let trigger = PressureTrigger::new(
+///     PressureResource::Memory,
+///     PressureStallKind::Some,
+///     Duration::from_millis(150),
+///     Duration::from_secs(1),
+/// )
+/// .expect("failed to create PSI trigger");
+/// loop {
+///     if trigger.wait(None).expect("failed to wait for PSI event") {
+///         println!("memory pressure!");
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PressureTrigger {
+    file: File,
+}
+
+impl PressureTrigger {
+    /// Creates a trigger on the system-wide pressure of `resource`.
+    pub fn new(
+        resource: PressureResource,
+        kind: PressureStallKind,
+        stall: Duration,
+        window: Duration,
+    ) -> io::Result<PressureTrigger> {
+        PressureTrigger::create(None, resource, kind, stall, window)
+    }
+
+    /// Creates a trigger on the pressure of `resource` in `cgroup` (a path in the cgroup v2
+    /// hierarchy, such as the one returned by [`Process::cgroup`][crate::Process#method.cgroup]).
+    pub fn new_for_cgroup(
+        cgroup: &str,
+        resource: PressureResource,
+        kind: PressureStallKind,
+        stall: Duration,
+        window: Duration,
+    ) -> io::Result<PressureTrigger> {
+        PressureTrigger::create(Some(cgroup), resource, kind, stall, window)
+    }
+
+    fn create(
+        cgroup: Option<&str>,
+        resource: PressureResource,
+        kind: PressureStallKind,
+        stall: Duration,
+        window: Duration,
+    ) -> io::Result<PressureTrigger> {
+        let path = get_pressure_path(cgroup, resource)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "cgroup not found"))?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(path)?;
+        let kind = match kind {
+            PressureStallKind::Some => "some",
+            PressureStallKind::Full => "full",
+        };
+        // The trigger is written in one go, including the terminating null byte.
+        file.write_all(
+            format!("{} {} {}\0", kind, stall.as_micros(), window.as_micros()).as_bytes(),
+        )?;
+        Ok(PressureTrigger { file })
+    }
+
+    /// Waits for the trigger to fire, at most `timeout` (or forever if `None`). Returns `false`
+    /// if the timeout expired.
+    pub fn wait(&self, timeout: Option<Duration>) -> io::Result<bool> {
+        let mut fds = pollfd {
+            fd: self.file.as_raw_fd(),
+            events: POLLPRI,
+            revents: 0,
+        };
+        let timeout = match timeout {
+            Some(timeout) => timeout.as_millis().min(c_int::MAX as u128) as c_int,
+            None => -1,
+        };
+        loop {
+            match unsafe { poll(&mut fds, 1, timeout) } {
+                0 => return Ok(false),
+                n if n > 0 => {
+                    if fds.revents & POLLERR != 0 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotFound,
+                            "the monitored cgroup has been removed",
+                        ));
+                    }
+                    return Ok(fds.revents & POLLPRI != 0);
+                }
+                _ => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_pressure;
+
+    #[test]
+    fn check_parse_pressure() {
+        let pressure = parse_pressure(
+            "\
+some avg10=11.41 avg60=7.27 avg300=6.33 total=95892815
+full avg10=0.09 avg60=0.06 avg300=0.00 total=5474843
+",
+        );
+        let some = pressure.some.expect("no some line");
+        assert_eq!(some.avg10, 11.41);
+        assert_eq!(some.avg60, 7.27);
+        assert_eq!(some.avg300, 6.33);
+        assert_eq!(some.total, 95892815);
+        let full = pressure.full.expect("no full line");
+        assert_eq!(full.avg10, 0.09);
+        assert_eq!(full.total, 5474843);
+
+        // Interrupts pressure only has a "full" line.
+        let pressure = parse_pressure("full avg10=0.00 avg60=0.00 avg300=0.00 total=12\n");
+        assert!(pressure.some.is_none());
+        assert_eq!(pressure.full.map(|f| f.total), Some(12));
+    }
+}
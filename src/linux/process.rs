@@ -7,17 +7,27 @@
 use std::collections::HashMap;
 use std::fmt;
 use std::fs::File;
+use std::io;
 use std::path::{Path, PathBuf};
 
-use libc::{c_int, gid_t, kill, uid_t};
+use libc::{
+    c_int, c_void, gid_t, kill, killpg, sigqueue as libc_sigqueue, sigval as sigval_t, syscall,
+    uid_t, SYS_ioprio_get, SYS_ioprio_set,
+};
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::sys::executable::{get_executable_info, is_executable_deleted, ExecutableInfo};
+use crate::sys::memory_map::{get_loaded_libraries, get_memory_maps, MemoryMap};
+use crate::sys::numa::parse_numa_maps;
+use crate::sys::thread::Thread;
+use crate::{DiskUsage, Pid, ProcessExt, Signal, Uid};
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProcessStatus {
-    /// Waiting in uninterruptible disk sleep.
+    /// Idle kernel thread (Linux 4.14 onward).
     Idle,
+    /// Waiting in uninterruptible disk sleep.
+    UninterruptibleDiskSleep,
     /// Running.
     Run,
     /// Sleeping in an interruptible waiting.
@@ -34,7 +44,7 @@ pub enum ProcessStatus {
     Wakekill,
     /// Waking (Linux 2.6.33 to 3.13 only).
     Waking,
-    /// Parked (Linux 3.9 to 3.13 only).
+    /// Parked (Linux 3.9 to 3.13 and 4.14 onward).
     Parked,
     /// Unknown.
     Unknown(u32),
@@ -43,7 +53,7 @@ pub enum ProcessStatus {
 impl From<u32> for ProcessStatus {
     fn from(status: u32) -> ProcessStatus {
         match status {
-            1 => ProcessStatus::Idle,
+            1 => ProcessStatus::UninterruptibleDiskSleep,
             2 => ProcessStatus::Run,
             3 => ProcessStatus::Sleep,
             4 => ProcessStatus::Stop,
@@ -58,7 +68,8 @@ impl From<char> for ProcessStatus {
         match status {
             'R' => ProcessStatus::Run,
             'S' => ProcessStatus::Sleep,
-            'D' => ProcessStatus::Idle,
+            'D' => ProcessStatus::UninterruptibleDiskSleep,
+            'I' => ProcessStatus::Idle,
             'Z' => ProcessStatus::Zombie,
             'T' => ProcessStatus::Stop,
             't' => ProcessStatus::Tracing,
@@ -76,6 +87,7 @@ impl ProcessStatus {
     pub fn as_str(&self) -> &str {
         match *self {
             ProcessStatus::Idle => "Idle",
+            ProcessStatus::UninterruptibleDiskSleep => "UninterruptibleDiskSleep",
             ProcessStatus::Run => "Runnable",
             ProcessStatus::Sleep => "Sleeping",
             ProcessStatus::Stop => "Stopped",
@@ -96,6 +108,229 @@ impl fmt::Display for ProcessStatus {
     }
 }
 
+/// Detailed memory information of a process, read from `/proc/[pid]/status`.
+///
+/// All values are in kB. It is returned by
+/// [`Process::memory_details`][crate::Process#method.memory_details].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for process in s.get_processes().values() {
+///     let details = process.memory_details();
+///     println!(
+///         "{}: peak memory {} kB, swap {} kB",
+///         process.name(),
+///         details.peak_memory,
+///         details.swap,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessMemoryDetails {
+    /// Peak virtual memory size (`VmPeak`).
+    pub peak_virtual_memory: u64,
+    /// Peak resident set size, also called "high water mark" (`VmHWM`).
+    pub peak_memory: u64,
+    /// Locked memory size (`VmLck`).
+    pub locked_memory: u64,
+    /// Pinned memory size, pages which cannot be moved (`VmPin`).
+    pub pinned_memory: u64,
+    /// Size of the data segment (`VmData`).
+    pub data: u64,
+    /// Size of the stack segment (`VmStk`).
+    pub stack: u64,
+    /// Size of the text segment (`VmExe`).
+    pub text: u64,
+    /// Size of the shared library code (`VmLib`).
+    pub libraries: u64,
+    /// Size of the page table entries (`VmPTE`).
+    pub page_tables: u64,
+    /// Swapped-out virtual memory size, shared memory excluded (`VmSwap`).
+    pub swap: u64,
+    /// Size of the resident anonymous memory (`RssAnon`).
+    pub resident_anonymous: u64,
+    /// Size of the resident file mappings (`RssFile`).
+    pub resident_file: u64,
+    /// Size of the resident shared memory (`RssShmem`).
+    pub resident_shared: u64,
+    /// Size of the hugetlb memory portions (`HugetlbPages`).
+    pub hugetlb_pages: u64,
+}
+
+/// Returns the realtime signal `SIGRTMIN + offset`, or `None` if it is bigger than `SIGRTMAX`
+/// (or if `offset` is negative).
+///
+/// The signal can then be sent with [`Process::kill_raw`][crate::Process#method.kill_raw],
+/// [`Process::kill_group`][crate::Process#method.kill_group] or
+/// [`Process::sigqueue`][crate::Process#method.sigqueue].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// if let (Some(process), Some(signal)) = (s.get_process(1337), sysinfo::realtime_signal(3)) {
+///     process.kill_raw(signal);
+/// }
+/// ```
+pub fn realtime_signal(offset: i32) -> Option<i32> {
+    let (min, max) = (libc::SIGRTMIN(), libc::SIGRTMAX());
+    if offset < 0 || offset > max - min {
+        None
+    } else {
+        Some(min + offset)
+    }
+}
+
+const IOPRIO_WHO_PROCESS: c_int = 1;
+const IOPRIO_CLASS_SHIFT: c_int = 13;
+
+/// I/O scheduling class of a process. See `ioprio_set(2)` for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoPriorityClass {
+    /// No class set: the I/O priority is derived from the CPU nice value of the process and the
+    /// class is the best-effort one.
+    None,
+    /// Real-time class: the process is always given first access to the disk.
+    RealTime,
+    /// Best-effort class, the default one.
+    BestEffort,
+    /// Idle class: the process only gets disk time when no other process needs it.
+    Idle,
+}
+
+/// I/O scheduling priority of a process.
+///
+/// It is returned by [`Process::io_priority`][crate::Process#method.io_priority].
+///
+/// ```no_run
+/// use sysinfo::{IoPriority, IoPriorityClass, ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for process in s.get_process_by_name("backup") {
+///     let idle = IoPriority { class: IoPriorityClass::Idle, level: 0 };
+///     if let Err(e) = process.set_io_priority(idle) {
+///         eprintln!("failed to update the I/O priority of {}: {}", process.pid(), e);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoPriority {
+    /// Scheduling class.
+    pub class: IoPriorityClass,
+    /// Priority level inside the class, from 0 (highest priority) to 7 (lowest). It is only
+    /// used by the real-time and best-effort classes and ignored for the other ones.
+    pub level: u8,
+}
+
+impl IoPriority {
+    fn from_raw(value: c_int) -> IoPriority {
+        IoPriority {
+            class: match value >> IOPRIO_CLASS_SHIFT {
+                1 => IoPriorityClass::RealTime,
+                2 => IoPriorityClass::BestEffort,
+                3 => IoPriorityClass::Idle,
+                _ => IoPriorityClass::None,
+            },
+            level: (value & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u8,
+        }
+    }
+
+    fn to_raw(self) -> io::Result<c_int> {
+        let (class, level) = match self.class {
+            IoPriorityClass::None => (0, 0),
+            IoPriorityClass::RealTime => (1, self.level),
+            IoPriorityClass::BestEffort => (2, self.level),
+            IoPriorityClass::Idle => (3, 0),
+        };
+        if level > 7 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "I/O priority level must be between 0 and 7",
+            ));
+        }
+        Ok(class << IOPRIO_CLASS_SHIFT | c_int::from(level))
+    }
+}
+
+/// Scheduler statistics of a process (or of a thread), read from `/proc/[pid]/schedstat`.
+///
+/// All times are in nanoseconds. It is returned by
+/// [`Process::scheduler_stats`][crate::Process#method.scheduler_stats].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for process in s.get_processes().values() {
+///     let stats = process.scheduler_stats();
+///     println!(
+///         "{}: waited {} ns on the run queue since last refresh",
+///         process.name(),
+///         stats.wait_time,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SchedulerStats {
+    /// Total time spent on the CPU.
+    pub total_cpu_time: u64,
+    /// Time spent on the CPU since the last refresh.
+    pub cpu_time: u64,
+    /// Total time spent waiting on a run queue.
+    pub total_wait_time: u64,
+    /// Time spent waiting on a run queue since the last refresh.
+    pub wait_time: u64,
+    /// Total number of timeslices run on the CPU.
+    pub total_timeslices: u64,
+    /// Number of timeslices run on the CPU since the last refresh.
+    pub timeslices: u64,
+}
+
+// Raw values of `/proc/[pid]/schedstat`, kept to compute the differences between two refreshes.
+#[derive(Default)]
+pub(crate) struct SchedulerValues {
+    cpu_time: u64,
+    wait_time: u64,
+    timeslices: u64,
+    old_cpu_time: u64,
+    old_wait_time: u64,
+    old_timeslices: u64,
+}
+
+impl SchedulerValues {
+    pub(crate) fn update(&mut self, path: &Path) {
+        let data = match super::system::get_all_data(path.join("schedstat"), 64) {
+            Ok(d) => d,
+            Err(_) => return,
+        };
+        // The file contains: "[time on cpu] [time waiting on a runqueue] [timeslices]".
+        let mut parts = data.split_whitespace().map(|x| x.parse::<u64>().ok());
+        if let (Some(Some(cpu_time)), Some(Some(wait_time)), Some(Some(timeslices))) =
+            (parts.next(), parts.next(), parts.next())
+        {
+            self.old_cpu_time = self.cpu_time;
+            self.old_wait_time = self.wait_time;
+            self.old_timeslices = self.timeslices;
+            self.cpu_time = cpu_time;
+            self.wait_time = wait_time;
+            self.timeslices = timeslices;
+        }
+    }
+
+    pub(crate) fn stats(&self) -> SchedulerStats {
+        SchedulerStats {
+            total_cpu_time: self.cpu_time,
+            cpu_time: self.cpu_time.saturating_sub(self.old_cpu_time),
+            total_wait_time: self.wait_time,
+            wait_time: self.wait_time.saturating_sub(self.old_wait_time),
+            total_timeslices: self.timeslices,
+            timeslices: self.timeslices.saturating_sub(self.old_timeslices),
+        }
+    }
+}
+
 /// Struct containing a process' information.
 pub struct Process {
     pub(crate) name: String,
@@ -108,6 +343,7 @@ pub struct Process {
     pub(crate) root: PathBuf,
     pub(crate) memory: u64,
     pub(crate) virtual_memory: u64,
+    memory_details: ProcessMemoryDetails,
     utime: u64,
     stime: u64,
     old_utime: u64,
@@ -120,13 +356,24 @@ pub struct Process {
     /// Group id of the process owner.
     pub gid: gid_t,
     pub(crate) status: ProcessStatus,
-    /// Tasks run by this process.
-    pub tasks: HashMap<Pid, Process>,
+    status_description: String,
+    pub(crate) kernel_thread: bool,
+    pub(crate) group_id: Pid,
+    pub(crate) session_id: Pid,
+    pub(crate) cgroup: String,
+    namespace_pids: Vec<Pid>,
+    namespace_tgids: Vec<Pid>,
+    namespace_pgids: Vec<Pid>,
+    namespace_sids: Vec<Pid>,
+    // Only used by the "root" process of `System`, which holds all the processes.
+    pub(crate) tasks: HashMap<Pid, Process>,
     pub(crate) stat_file: Option<File>,
     old_read_bytes: u64,
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    pub(crate) scheduler_values: SchedulerValues,
+    pub(crate) threads: HashMap<Pid, Thread>,
 }
 
 impl ProcessExt for Process {
@@ -142,6 +389,7 @@ impl ProcessExt for Process {
             root: PathBuf::new(),
             memory: 0,
             virtual_memory: 0,
+            memory_details: ProcessMemoryDetails::default(),
             cpu_usage: 0.,
             utime: 0,
             stime: 0,
@@ -152,6 +400,15 @@ impl ProcessExt for Process {
             uid: 0,
             gid: 0,
             status: ProcessStatus::Unknown(0),
+            status_description: String::new(),
+            kernel_thread: false,
+            group_id: 0,
+            session_id: 0,
+            cgroup: String::new(),
+            namespace_pids: Vec::new(),
+            namespace_tgids: Vec::new(),
+            namespace_pgids: Vec::new(),
+            namespace_sids: Vec::new(),
             tasks: if pid == 0 {
                 HashMap::with_capacity(1000)
             } else {
@@ -162,6 +419,8 @@ impl ProcessExt for Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            scheduler_values: SchedulerValues::default(),
+            threads: HashMap::new(),
         }
     }
 
@@ -231,6 +490,434 @@ impl ProcessExt for Process {
             total_read_bytes: self.read_bytes,
         }
     }
+
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn thread_count(&self) -> Option<usize> {
+        Some(self.threads.len())
+    }
+}
+
+impl Process {
+    /// Returns the description of the process state as written in the `State` line of
+    /// `/proc/[pid]/status` (for example "sleeping", "disk sleep" or "idle").
+    ///
+    /// It is only updated when the process is refreshed if
+    /// [`System::set_process_details`][crate::System#method.set_process_details] is enabled.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{:?} ({})", process.status(), process.status_description());
+    /// }
+    /// ```
+    pub fn status_description(&self) -> &str {
+        &self.status_description
+    }
+
+    /// Returns `true` if this process is a kernel thread (the `PF_KTHREAD` flag is set in
+    /// `/proc/[pid]/stat`).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let nb_kernel_threads = s
+    ///     .get_processes()
+    ///     .values()
+    ///     .filter(|p| p.is_kernel_thread())
+    ///     .count();
+    /// println!("{} kernel threads", nb_kernel_threads);
+    /// ```
+    pub fn is_kernel_thread(&self) -> bool {
+        self.kernel_thread
+    }
+
+    /// Returns the id of the process group of the process.
+    pub fn group_id(&self) -> Pid {
+        self.group_id
+    }
+
+    /// Returns the id of the session of the process.
+    pub fn session_id(&self) -> Pid {
+        self.session_id
+    }
+
+    /// Sends the raw `signal` to the process. Contrary to [`ProcessExt::kill`], any signal
+    /// number can be used, including realtime signals (see [`realtime_signal`][crate::realtime_signal]).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     process.kill_raw(libc::SIGWINCH);
+    /// }
+    /// ```
+    pub fn kill_raw(&self, signal: i32) -> bool {
+        unsafe { kill(self.pid, signal) == 0 }
+    }
+
+    /// Sends the raw `signal` to all the processes of the process group of this process, using
+    /// `killpg`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     process.kill_group(libc::SIGTERM);
+    /// }
+    /// ```
+    pub fn kill_group(&self, signal: i32) -> bool {
+        // `killpg` with 0 would send the signal to our own process group.
+        self.group_id > 0 && unsafe { killpg(self.group_id, signal) == 0 }
+    }
+
+    /// Sends the raw `signal` to the process along with the integer `value`, using `sigqueue`.
+    /// The value is available in the `si_value` field of the `siginfo_t` received by the
+    /// signal handler (installed with `SA_SIGINFO`).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let (Some(process), Some(signal)) = (s.get_process(1337), sysinfo::realtime_signal(2)) {
+    ///     process.sigqueue(signal, 42);
+    /// }
+    /// ```
+    pub fn sigqueue(&self, signal: i32, value: i32) -> bool {
+        #[repr(C)]
+        union SigVal {
+            int: c_int,
+            ptr: *mut c_void,
+        }
+
+        // `libc::sigval` only exposes the pointer member of the C union.
+        let mut sigval = SigVal {
+            ptr: std::ptr::null_mut(),
+        };
+        sigval.int = value;
+        unsafe {
+            let sigval = sigval_t {
+                sival_ptr: sigval.ptr,
+            };
+            libc_sigqueue(self.pid, signal, sigval) == 0
+        }
+    }
+
+    /// Returns the control group of the process, read from `/proc/[pid]/cgroup` when the process
+    /// is first listed.
+    ///
+    /// With cgroup v2, it is the path in the unified hierarchy. With cgroup v1 (or hybrid setups),
+    /// it is the path in the `name=systemd` hierarchy if any, otherwise the one of the first
+    /// listed hierarchy. An empty string is returned if it cannot be retrieved.
+    ///
+    /// It is only updated when the process is refreshed if
+    /// [`System::set_process_details`][crate::System#method.set_process_details] is enabled.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{}", process.cgroup());
+    /// }
+    /// ```
+    pub fn cgroup(&self) -> &str {
+        &self.cgroup
+    }
+
+    /// Returns the pids of the process in each of the PID namespaces it belongs to (the `NSpid`
+    /// field of `/proc/[pid]/status`), from the namespace of `/proc` to the innermost one.
+    ///
+    /// It is empty if this information isn't available (before Linux 4.1).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{:?}", process.namespace_pids());
+    /// }
+    /// ```
+    pub fn namespace_pids(&self) -> &[Pid] {
+        &self.namespace_pids
+    }
+
+    /// Returns the thread group ids of the process in each of its PID namespaces (the `NStgid`
+    /// field of `/proc/[pid]/status`). See [`Process::namespace_pids`][crate::Process#method.namespace_pids].
+    pub fn namespace_tgids(&self) -> &[Pid] {
+        &self.namespace_tgids
+    }
+
+    /// Returns the process group ids of the process in each of its PID namespaces (the `NSpgid`
+    /// field of `/proc/[pid]/status`). See [`Process::namespace_pids`][crate::Process#method.namespace_pids].
+    pub fn namespace_pgids(&self) -> &[Pid] {
+        &self.namespace_pgids
+    }
+
+    /// Returns the session ids of the process in each of its PID namespaces (the `NSsid` field
+    /// of `/proc/[pid]/status`). See [`Process::namespace_pids`][crate::Process#method.namespace_pids].
+    pub fn namespace_sids(&self) -> &[Pid] {
+        &self.namespace_sids
+    }
+
+    /// Returns the pid of the process in its innermost PID namespace: for a process running in a
+    /// container, it is the pid seen from inside the container. It is the same as
+    /// [`ProcessExt::pid`] for processes which aren't in a nested PID namespace.
+    ///
+    /// `None` is returned if this information isn't available (before Linux 4.1).
+    ///
+    /// To do the opposite, look at
+    /// [`System::get_process_by_container_pid`][crate::System#method.get_process_by_container_pid].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     if let Some(pid) = process.container_pid() {
+    ///         println!("{} is {} in its container", process.pid(), pid);
+    ///     }
+    /// }
+    /// ```
+    pub fn container_pid(&self) -> Option<Pid> {
+        self.namespace_pids.last().copied()
+    }
+
+    /// Returns the inode number of the innermost PID namespace of the process, read from
+    /// `/proc/[pid]/ns/pid`. Two processes with the same value are in the same PID namespace.
+    ///
+    /// This information isn't stored when refreshing processes. `None` is returned if it cannot
+    /// be read (because you don't have enough rights for example).
+    pub fn pid_namespace(&self) -> Option<u64> {
+        // The link looks like this: "pid:[4026531836]".
+        let link = std::fs::read_link(self.proc_path().join("ns/pid")).ok()?;
+        link.to_str()?
+            .strip_prefix("pid:[")?
+            .strip_suffix(']')?
+            .parse()
+            .ok()
+    }
+
+    /// Returns the detailed memory information of the process (peak memory, swap, etc).
+    ///
+    /// These values are read from `/proc/[pid]/status` when the process is refreshed. They are
+    /// all set to `0` for kernel threads.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{} kB (peak: {} kB)", process.memory(), process.memory_details().peak_memory);
+    /// }
+    /// ```
+    pub fn memory_details(&self) -> ProcessMemoryDetails {
+        self.memory_details
+    }
+
+    /// Returns the scheduler statistics of the process: time spent on the CPU, time spent
+    /// waiting on a run queue and number of timeslices.
+    ///
+    /// The values are read from `/proc/[pid]/schedstat` when the process is refreshed, if
+    /// [`System::set_process_details`][crate::System#method.set_process_details] is enabled.
+    /// They are all set to `0` otherwise, or if the kernel wasn't built with scheduler
+    /// statistics. The statistics of
+    /// each thread are available with [`Thread::scheduler_stats`][crate::Thread#method.scheduler_stats].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{} ns waiting", process.scheduler_stats().wait_time);
+    /// }
+    /// ```
+    pub fn scheduler_stats(&self) -> SchedulerStats {
+        self.scheduler_values.stats()
+    }
+
+    /// Returns the I/O scheduling priority of the process, using `ioprio_get`.
+    ///
+    /// This information isn't stored when refreshing processes. `None` is returned if it cannot
+    /// be retrieved (because the process is gone for example).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{:?}", process.io_priority());
+    /// }
+    /// ```
+    pub fn io_priority(&self) -> Option<IoPriority> {
+        let ret = unsafe { syscall(SYS_ioprio_get, IOPRIO_WHO_PROCESS, self.pid) };
+        if ret < 0 {
+            None
+        } else {
+            Some(IoPriority::from_raw(ret as c_int))
+        }
+    }
+
+    /// Sets the I/O scheduling priority of the process, using `ioprio_set`. An
+    /// [`InvalidInput`][std::io::ErrorKind::InvalidInput] error is returned if the level of a
+    /// real-time or best-effort priority is above 7.
+    ///
+    /// Setting the real-time class or changing the priority of a process of another user
+    /// requires privileges. Note that, like `ionice`, only the main thread of the process is
+    /// updated: the other threads keep their priority.
+    ///
+    /// ```no_run
+    /// use sysinfo::{IoPriority, IoPriorityClass, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     process
+    ///         .set_io_priority(IoPriority { class: IoPriorityClass::BestEffort, level: 7 })
+    ///         .expect("failed to set the I/O priority");
+    /// }
+    /// ```
+    pub fn set_io_priority(&self, priority: IoPriority) -> io::Result<()> {
+        let priority = priority.to_raw()?;
+        if unsafe { syscall(SYS_ioprio_set, IOPRIO_WHO_PROCESS, self.pid, priority) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Returns the threads of the process, indexed by their thread id.
+    ///
+    /// The threads are refreshed alongside their process. To only refresh the threads of a
+    /// process, use [`System::refresh_threads`][crate::System#method.refresh_threads].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     for (tid, thread) in process.threads() {
+    ///         println!("{}:{} => {}%", tid, thread.name(), thread.cpu_usage());
+    ///     }
+    /// }
+    /// ```
+    pub fn threads(&self) -> &HashMap<Pid, Thread> {
+        &self.threads
+    }
+
+    /// Returns the memory mappings of the process, read from `/proc/[pid]/maps`.
+    ///
+    /// This information isn't stored when refreshing processes: the file is read every time this
+    /// method is called. If it cannot be read (because the process is gone or because you don't
+    /// have enough rights), an empty list is returned.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     for map in process.memory_maps().iter().filter(|m| m.deleted) {
+    ///         println!("{} was deleted", map.pathname);
+    ///     }
+    /// }
+    /// ```
+    pub fn memory_maps(&self) -> Vec<MemoryMap> {
+        get_memory_maps(&self.proc_path())
+    }
+
+    /// Returns the memory (in kB) mapped by the process on each NUMA node, indexed by node
+    /// identifier (see [`System::get_numa_nodes`][crate::System#method.get_numa_nodes]).
+    ///
+    /// This information isn't stored when refreshing processes, `/proc/[pid]/numa_maps` is read
+    /// every time this method is called. If it cannot be read (because the kernel doesn't
+    /// support NUMA or because you don't have enough rights), an empty map is returned.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     for (node, memory) in process.numa_memory() {
+    ///         println!("node {}: {} kB", node, memory);
+    ///     }
+    /// }
+    /// ```
+    pub fn numa_memory(&self) -> HashMap<u32, u64> {
+        super::system::get_all_data(self.proc_path().join("numa_maps"), 16_384)
+            .map(|data| parse_numa_maps(&data))
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the executable of the process has been deleted or replaced by another
+    /// file (after a package upgrade for example) since the process was started.
+    ///
+    /// This information isn't stored when refreshing processes. If it cannot be determined, `false`
+    /// is returned.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.get_processes().values() {
+    ///     if process.is_exe_deleted() {
+    ///         println!("{} needs to be restarted", process.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn is_exe_deleted(&self) -> bool {
+        is_executable_deleted(&self.proc_path())
+    }
+
+    /// Returns information about the executable of the process (ELF class, architecture,
+    /// interpreter and build-id), read from `/proc/[pid]/exe`.
+    ///
+    /// This information isn't stored when refreshing processes: the file is read every time this
+    /// method is called. `None` is returned if it cannot be read (kernel threads, not enough
+    /// rights, etc) or if it isn't an ELF file.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     if let Some(info) = process.exe_info() {
+    ///         println!("{}: {:?} {}", process.name(), info.class, info.architecture());
+    ///     }
+    /// }
+    /// ```
+    pub fn exe_info(&self) -> Option<ExecutableInfo> {
+        get_executable_info(&self.proc_path())
+    }
+
+    /// Returns the shared libraries loaded by the process (one entry per library file), read
+    /// from `/proc/[pid]/maps`.
+    ///
+    /// The `deleted` field is set if the library file has been deleted or replaced since it was
+    /// loaded (after a package upgrade for example).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.get_processes().values() {
+    ///     if process.loaded_libraries().iter().any(|lib| lib.deleted) {
+    ///         println!("{} needs to be restarted", process.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn loaded_libraries(&self) -> Vec<MemoryMap> {
+        get_loaded_libraries(self.memory_maps())
+    }
+
+    pub(crate) fn proc_path(&self) -> PathBuf {
+        Path::new("/proc").join(self.pid.to_string())
+    }
 }
 
 impl Drop for Process {
@@ -266,6 +953,116 @@ pub fn has_been_updated(p: &Process) -> bool {
     p.updated
 }
 
+fn parse_pid_list(pids: &mut Vec<Pid>, value: &str) {
+    pids.clear();
+    pids.extend(
+        value
+            .split_whitespace()
+            .filter_map(|x| x.parse::<Pid>().ok()),
+    );
+}
+
+pub(crate) fn update_process_status_data(p: &mut Process, path: &Path) {
+    let data = match super::system::get_all_data(path.join("status"), 16_384) {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+    for line in data.lines() {
+        let mut parts = line.splitn(2, ':');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key, value.trim()),
+            _ => continue,
+        };
+        match key {
+            "State" => {
+                // The line looks like this: "State:\tS (sleeping)".
+                p.status_description.clear();
+                if let Some(pos) = value.find('(') {
+                    p.status_description
+                        .push_str(value[pos + 1..].trim_end_matches(')'));
+                }
+            }
+            "NSpid" => parse_pid_list(&mut p.namespace_pids, value),
+            "NStgid" => parse_pid_list(&mut p.namespace_tgids, value),
+            "NSpgid" => parse_pid_list(&mut p.namespace_pgids, value),
+            "NSsid" => parse_pid_list(&mut p.namespace_sids, value),
+            // We're looking at the second entry to get the effective u/gid.
+            "Uid" => {
+                if let Some(uid) = value.split_whitespace().nth(1).and_then(|x| x.parse().ok()) {
+                    p.uid = uid;
+                }
+            }
+            "Gid" => {
+                if let Some(gid) = value.split_whitespace().nth(1).and_then(|x| x.parse().ok()) {
+                    p.gid = gid;
+                }
+            }
+            _ => {
+                let details = &mut p.memory_details;
+                let field = match key {
+                    "VmPeak" => &mut details.peak_virtual_memory,
+                    "VmHWM" => &mut details.peak_memory,
+                    "VmLck" => &mut details.locked_memory,
+                    "VmPin" => &mut details.pinned_memory,
+                    "VmData" => &mut details.data,
+                    "VmStk" => &mut details.stack,
+                    "VmExe" => &mut details.text,
+                    "VmLib" => &mut details.libraries,
+                    "VmPTE" => &mut details.page_tables,
+                    "VmSwap" => &mut details.swap,
+                    "RssAnon" => &mut details.resident_anonymous,
+                    "RssFile" => &mut details.resident_file,
+                    "RssShmem" => &mut details.resident_shared,
+                    "HugetlbPages" => &mut details.hugetlb_pages,
+                    _ => continue,
+                };
+                // The line looks like this: "VmPeak:\t    3348 kB". Despite the "kB", the value is
+                // in KiB so it is converted.
+                *field = value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|x| x.parse::<u64>().ok())
+                    .map(|x| x * 128 / 125)
+                    .unwrap_or(0);
+            }
+        }
+    }
+}
+
+pub(crate) fn update_process_cgroup(p: &mut Process, path: &Path) {
+    let mut path = PathBuf::from(path);
+    path.push("cgroup");
+    // A process can be moved to another control group at any time.
+    if let Ok(data) = super::system::get_all_data(&path, 16_384) {
+        p.cgroup = parse_cgroup(&data);
+    }
+}
+
+fn parse_cgroup(data: &str) -> String {
+    let mut cgroup = None;
+    // On hybrid setups, the unified hierarchy is listed too but isn't the one being used.
+    let unified_only = data.lines().nth(1).is_none();
+
+    // Each line looks like this: "hierarchy-ID:controller-list:cgroup-path".
+    for line in data.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (id, controllers, path) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(id), Some(controllers), Some(path)) => (id, controllers, path),
+            _ => continue,
+        };
+        if controllers == "name=systemd" {
+            return path.to_owned();
+        } else if id == "0" && controllers.is_empty() {
+            if unified_only {
+                return path.to_owned();
+            }
+        } else if cgroup.is_none() {
+            cgroup = Some(path);
+        }
+    }
+    cgroup.unwrap_or("").to_owned()
+}
+
 pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
     let mut path = PathBuf::from(path);
     path.push("io");
@@ -300,3 +1097,50 @@ pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cgroup, IoPriority, IoPriorityClass, IOPRIO_CLASS_SHIFT};
+
+    #[test]
+    fn check_parse_cgroup() {
+        assert_eq!(
+            parse_cgroup("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            "/user.slice/user-1000.slice/session-2.scope"
+        );
+        assert_eq!(
+            parse_cgroup(
+                "\
+12:cpu,cpuacct:/user.slice
+1:name=systemd:/user.slice/user-1000.slice/session-2.scope
+0::/
+"
+            ),
+            "/user.slice/user-1000.slice/session-2.scope"
+        );
+        assert_eq!(parse_cgroup("4:memory:/docker/abc\n0::/\n"), "/docker/abc");
+        assert_eq!(parse_cgroup(""), "");
+    }
+
+    #[test]
+    fn check_io_priority_to_raw() {
+        let priority = |class, level| IoPriority { class, level }.to_raw().ok();
+
+        assert_eq!(
+            priority(IoPriorityClass::BestEffort, 4),
+            Some(2 << IOPRIO_CLASS_SHIFT | 4)
+        );
+        assert_eq!(
+            priority(IoPriorityClass::RealTime, 7),
+            Some(1 << IOPRIO_CLASS_SHIFT | 7)
+        );
+        assert_eq!(priority(IoPriorityClass::RealTime, 8), None);
+        assert_eq!(priority(IoPriorityClass::BestEffort, 255), None);
+        // The level is ignored for these classes.
+        assert_eq!(
+            priority(IoPriorityClass::Idle, 200),
+            Some(3 << IOPRIO_CLASS_SHIFT)
+        );
+        assert_eq!(priority(IoPriorityClass::None, 5), Some(0));
+    }
+}
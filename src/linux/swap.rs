@@ -0,0 +1,242 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::system::get_all_data;
+
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+use std::path::{Path, PathBuf};
+
+/// Type of a swap area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapType {
+    /// Disk partition (or any block device other than zram).
+    Partition,
+    /// Swap file.
+    File,
+    /// Compressed block device in RAM (zram).
+    Zram,
+    /// Unknown type.
+    Unknown,
+}
+
+/// Compression statistics of a zram device, read from `/sys/block/zram*/mm_stat`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ZramStats {
+    /// Uncompressed size of the data stored in the device (in bytes).
+    pub original_data_size: u64,
+    /// Compressed size of the data stored in the device (in bytes).
+    pub compressed_data_size: u64,
+    /// Memory used by the device, including the allocator overhead (in bytes).
+    pub memory_used: u64,
+    /// Maximum memory the device can use, `0` if unlimited (in bytes).
+    pub memory_limit: u64,
+    /// Highest memory used by the device (in bytes).
+    pub memory_used_max: u64,
+    /// Number of pages filled with the same value, which don't use any memory.
+    pub same_pages: u64,
+    /// Number of pages freed by compaction.
+    pub pages_compacted: u64,
+    /// Number of incompressible pages (Linux 4.19 and later).
+    pub huge_pages: u64,
+}
+
+impl ZramStats {
+    /// Returns the compression ratio (the uncompressed size divided by the memory used), or `0`
+    /// if the device is empty.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.memory_used == 0 {
+            0.
+        } else {
+            self.original_data_size as f64 / self.memory_used as f64
+        }
+    }
+}
+
+/// A swap area, read from `/proc/swaps`.
+///
+/// They are returned by [`System::get_swaps`][crate::System#method.get_swaps].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_swaps();
+/// for swap in s.get_swaps() {
+///     println!("{:?}: {} / {} kB", swap.path, swap.used, swap.size);
+///     if let Some(zram) = swap.zram {
+///         println!("compression ratio: {:.2}", zram.compression_ratio());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Swap {
+    /// Path of the device or file.
+    pub path: PathBuf,
+    /// Type of the swap area.
+    pub swap_type: SwapType,
+    /// Size of the swap area (in kB).
+    pub size: u64,
+    /// Used part of the swap area (in kB).
+    pub used: u64,
+    /// Priority of the swap area: the ones with a higher priority are used first.
+    pub priority: i32,
+    /// Compression statistics if it's a zram device.
+    pub zram: Option<ZramStats>,
+}
+
+// Paths in `/proc/swaps` have their spaces, tabulations, new lines and backslashes escaped as
+// octal values ("\040" for example).
+fn unescape(path: &str) -> PathBuf {
+    let bytes = path.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 4).and_then(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+        });
+        match escaped {
+            Some(c) if bytes[i] == b'\\' => {
+                out.push(c);
+                i += 4;
+            }
+            _ => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    PathBuf::from(OsString::from_vec(out))
+}
+
+fn get_zram_name(path: &Path) -> Option<&str> {
+    let name = path.strip_prefix("/dev").ok()?.to_str()?;
+    let id = name.strip_prefix("zram")?;
+    if !id.is_empty() && id.bytes().all(|c| c.is_ascii_digit()) {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn parse_mm_stat(data: &str) -> Option<ZramStats> {
+    let mut values = data
+        .split_whitespace()
+        .map(|v| v.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    // The number of columns grew with kernel versions.
+    if values.len() < 5 {
+        return None;
+    }
+    values.resize(8, 0);
+    Some(ZramStats {
+        original_data_size: values[0],
+        compressed_data_size: values[1],
+        memory_used: values[2],
+        memory_limit: values[3],
+        memory_used_max: values[4],
+        same_pages: values[5],
+        pages_compacted: values[6],
+        huge_pages: values[7],
+    })
+}
+
+pub(crate) fn parse_swaps<F: Fn(&str) -> Option<ZramStats>>(data: &str, get_zram: F) -> Vec<Swap> {
+    // The first line is the header:
+    // "Filename	Type	Size	Used	Priority"
+    data.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let path = unescape(parts.next()?);
+            let kind = parts.next()?;
+            // The values are in KiB, we convert them into kB.
+            let size = parts.next()?.parse::<u64>().ok()? * 128 / 125;
+            let used = parts.next()?.parse::<u64>().ok()? * 128 / 125;
+            let priority = parts.next()?.parse().ok()?;
+            let zram_name = get_zram_name(&path);
+            let swap_type = match kind {
+                "partition" if zram_name.is_some() => SwapType::Zram,
+                "partition" => SwapType::Partition,
+                "file" => SwapType::File,
+                _ => SwapType::Unknown,
+            };
+            let zram = match swap_type {
+                SwapType::Zram => zram_name.and_then(&get_zram),
+                _ => None,
+            };
+            Some(Swap {
+                path,
+                swap_type,
+                size,
+                used,
+                priority,
+                zram,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn get_swaps() -> Vec<Swap> {
+    match get_all_data("/proc/swaps", 1024) {
+        Ok(data) => parse_swaps(&data, |name| {
+            parse_mm_stat(
+                &get_all_data(Path::new("/sys/block").join(name).join("mm_stat"), 256).ok()?,
+            )
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_mm_stat, parse_swaps, SwapType};
+    use std::path::Path;
+
+    #[test]
+    fn check_parse_swaps() {
+        let swaps = parse_swaps(
+            "\
+Filename				Type		Size		Used		Priority
+/dev/sda2                               partition	8388604		1024		-2
+/swap\\040file                           file		1000		0		-3
+/dev/zram0                              partition	4194300		250		100
+",
+            |name| {
+                assert_eq!(name, "zram0");
+                parse_mm_stat("4096 1024 2048 0 8192 3 0 1")
+            },
+        );
+        assert_eq!(swaps.len(), 3);
+        assert_eq!(swaps[0].path, Path::new("/dev/sda2"));
+        assert_eq!(swaps[0].swap_type, SwapType::Partition);
+        assert_eq!(swaps[0].size, 8388604 * 128 / 125);
+        assert_eq!(swaps[0].used, 1024 * 128 / 125);
+        assert_eq!(swaps[0].priority, -2);
+        assert!(swaps[0].zram.is_none());
+        assert_eq!(swaps[1].path, Path::new("/swap file"));
+        assert_eq!(swaps[1].swap_type, SwapType::File);
+        assert_eq!(swaps[1].size, 1024);
+        assert_eq!(swaps[2].swap_type, SwapType::Zram);
+        assert_eq!(swaps[2].priority, 100);
+        let zram = swaps[2].zram.expect("missing zram stats");
+        assert_eq!(zram.original_data_size, 4096);
+        assert_eq!(zram.memory_used, 2048);
+        assert_eq!(zram.same_pages, 3);
+        assert_eq!(zram.huge_pages, 1);
+        assert_eq!(zram.compression_ratio(), 2.);
+
+        // Older kernels only have 7 columns.
+        assert_eq!(
+            parse_mm_stat("1 2 3 4 5 6 7").map(|z| z.huge_pages),
+            Some(0)
+        );
+        assert!(parse_mm_stat("1 2").is_none());
+    }
+}
@@ -4,13 +4,25 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+use crate::sys::cgroup::CgroupLimits;
 use crate::sys::component::{self, Component};
 use crate::sys::disk;
+use crate::sys::interrupts::{parse_interrupts, Interrupt};
+use crate::sys::kernel_stats::KernelStats;
+use crate::sys::load_avg::{parse_load, parse_loadavg, DetailedLoadAvg};
+use crate::sys::memory::{parse_meminfo, MemoryInfo};
+use crate::sys::numa::{get_numa_nodes, NumaNode};
+use crate::sys::pressure::{get_pressure, Pressure, PressureResource};
 use crate::sys::process::*;
 use crate::sys::processor::*;
-use crate::{Disk, LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
-
-use libc::{self, c_char, gid_t, sysconf, uid_t, _SC_CLK_TCK, _SC_HOST_NAME_MAX, _SC_PAGESIZE};
+use crate::sys::swap::{get_swaps, Swap};
+use crate::sys::thread::{compute_thread_cpu_usage, refresh_threads};
+use crate::sys::vmstat::VmStat;
+use crate::{
+    Disk, LoadAvg, Networks, Pid, ProcessExt, ProcessesUsage, RefreshKind, SystemExt, Uid, User,
+};
+
+use libc::{self, c_char, sysconf, _SC_CLK_TCK, _SC_HOST_NAME_MAX, _SC_PAGESIZE};
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
 use std::fs::{self, File};
@@ -18,7 +30,7 @@ use std::io::{self, BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::utils::{into_iter, realpath};
 
@@ -68,6 +80,9 @@ pub(crate) fn get_max_nb_fds() -> isize {
     }
 }
 
+// Flag set in the `flags` field of `/proc/[pid]/stat` for kernel threads.
+const PF_KTHREAD: u32 = 0x0020_0000;
+
 macro_rules! to_str {
     ($e:expr) => {
         unsafe { std::str::from_utf8_unchecked($e) }
@@ -107,14 +122,15 @@ fn boot_time() -> u64 {
 /// Structs containing system's information.
 pub struct System {
     process_list: Process,
-    mem_total: u64,
-    mem_free: u64,
-    mem_available: u64,
-    mem_buffers: u64,
-    mem_page_cache: u64,
-    mem_slab_reclaimable: u64,
-    swap_total: u64,
-    swap_free: u64,
+    memory_info: MemoryInfo,
+    vmstat: VmStat,
+    swaps: Vec<Swap>,
+    kernel_stats: KernelStats,
+    interrupts: Vec<Interrupt>,
+    softirqs: Vec<Interrupt>,
+    cgroup_constrained: bool,
+    cgroup_limits: CgroupLimits,
+    process_details: bool,
     global_processor: Processor,
     processors: Vec<Processor>,
     page_size_kb: u64,
@@ -122,11 +138,19 @@ pub struct System {
     disks: Vec<Disk>,
     networks: Networks,
     uptime: u64,
+    idle_time: Duration,
     users: Vec<User>,
+    user_ids: HashMap<Uid, usize>,
     boot_time: u64,
 }
 
 impl System {
+    fn refresh_uptime(&mut self) {
+        let (uptime, idle_time) = get_uptime();
+        self.uptime = uptime;
+        self.idle_time = idle_time;
+    }
+
     fn clear_procs(&mut self) {
         if !self.processors.is_empty() {
             let (new, old) = get_raw_times(&self.global_processor);
@@ -138,6 +162,9 @@ impl System {
                     to_delete.push(*pid);
                 } else {
                     compute_cpu_usage(proc_, self.processors.len() as u64, total_time);
+                    for thread in proc_.threads.values_mut() {
+                        compute_thread_cpu_usage(thread, self.processors.len() as u64, total_time);
+                    }
                 }
             }
             for pid in to_delete {
@@ -190,6 +217,13 @@ impl System {
             }
             while let Some(Ok(line)) = it.next() {
                 if &line[..3] != b"cpu" {
+                    // The kernel activity counters follow the processors.
+                    let mut data = String::from_utf8_lossy(&line).into_owned();
+                    while let Some(Ok(line)) = it.next() {
+                        data.push('\n');
+                        data.push_str(&String::from_utf8_lossy(&line));
+                    }
+                    self.kernel_stats.update(&data, Instant::now());
                     break;
                 }
 
@@ -241,20 +275,553 @@ impl System {
             }
         }
     }
+
+    /// Refreshes the threads of the process with the given `pid`, without refreshing the
+    /// process information itself.
+    ///
+    /// Returns `false` if the process isn't in the processes list (or if its threads cannot be
+    /// listed anymore).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// if s.refresh_threads(1337) {
+    ///     if let Some(process) = s.get_process(1337) {
+    ///         println!("{} threads", process.threads().len());
+    ///     }
+    /// }
+    /// ```
+    pub fn refresh_threads(&mut self, pid: Pid) -> bool {
+        let p = match self.process_list.tasks.get_mut(&pid) {
+            Some(p) => p,
+            None => return false,
+        };
+        let path = p.proc_path();
+        if !refresh_threads(&mut p.threads, &path, self.process_details) {
+            return false;
+        }
+        if !self.processors.is_empty() {
+            self.refresh_processors(Some(1));
+            let (new, old) = get_raw_times(&self.global_processor);
+            let total_time = (if old >= new { 1 } else { new - old }) as f32;
+
+            if let Some(p) = self.process_list.tasks.get_mut(&pid) {
+                for thread in p.threads.values_mut() {
+                    compute_thread_cpu_usage(thread, self.processors.len() as u64, total_time);
+                }
+            }
+        }
+        true
+    }
+
+    // Returns the total, used and available memory of the control group if the memory getters
+    // report the constrained values. The values of the whole system are used if the memory
+    // used by the control group cannot be read.
+    fn get_constrained_memory(&self) -> Option<(u64, u64, u64)> {
+        if !self.cgroup_constrained {
+            return None;
+        }
+        let limits = &self.cgroup_limits;
+        let total = self.memory_info.total.min(limits.memory_limit?);
+        let used = limits.memory_usage?.min(total);
+        let reclaimable = limits.memory_reclaimable.unwrap_or(0).min(used);
+        Some((total, used, total - used + reclaimable))
+    }
+
+    /// Returns the resource limits of the control group of the current process. The limits
+    /// which aren't set are `None`.
+    ///
+    /// The limits are read by [`System::refresh_cgroup_limits`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroup_limits();
+    /// println!("{:?}", s.get_cgroup_limits());
+    /// ```
+    pub fn get_cgroup_limits(&self) -> CgroupLimits {
+        self.cgroup_limits
+    }
+
+    /// Refreshes the resource limits of the control group of the current process.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroup_limits();
+    /// ```
+    pub fn refresh_cgroup_limits(&mut self) {
+        self.cgroup_limits.refresh_memory();
+        self.cgroup_limits.refresh_cpu();
+    }
+
+    /// Returns the memory (in kB) the current process can use: the memory limit of its control
+    /// group if there is one and if it is lower than the total memory.
+    ///
+    /// The total memory is read by [`SystemExt::refresh_memory`] and the limit by
+    /// [`System::refresh_cgroup_limits`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_memory();
+    /// s.refresh_cgroup_limits();
+    /// // We use a quarter of the memory we can use for our cache.
+    /// let cache_size = s.get_effective_memory_limit() / 4;
+    /// ```
+    pub fn get_effective_memory_limit(&self) -> u64 {
+        let total = self.memory_info.total;
+        match self.cgroup_limits.memory_limit {
+            Some(limit) => total.min(limit),
+            None => total,
+        }
+    }
+
+    /// Returns the number of CPUs the current process can use: the CPU quota of its control
+    /// group if there is one and if it is lower than the number of processors.
+    ///
+    /// The quota is read by [`System::refresh_cgroup_limits`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cgroup_limits();
+    /// let nb_threads = s.get_effective_cpu_quota().ceil() as usize;
+    /// ```
+    pub fn get_effective_cpu_quota(&self) -> f64 {
+        let nb_processors = self.processors.len() as f64;
+        match self.cgroup_limits.cpu_quota {
+            Some(quota) => nb_processors.min(quota),
+            None => nb_processors,
+        }
+    }
+
+    /// If `constrained` is `true`, the memory getters of [`SystemExt`] (such as
+    /// [`SystemExt::get_total_memory`]) report the memory of the control group of the current
+    /// process when it has a memory limit: the total memory becomes the limit, the used memory
+    /// is the memory used by the control group (including the page cache) and the available
+    /// memory also counts the page cache which can be reclaimed. In this mode,
+    /// [`SystemExt::refresh_memory`] also refreshes the memory limit and usage of the control
+    /// group. If the memory used by the control group cannot be read, the values of the whole
+    /// system are reported.
+    ///
+    /// The swap getters and [`System::get_memory_info`] always report the values of the whole
+    /// system.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.set_cgroup_constrained(true);
+    /// s.refresh_memory();
+    /// println!("{} / {} kB", s.get_used_memory(), s.get_total_memory());
+    /// ```
+    pub fn set_cgroup_constrained(&mut self, constrained: bool) {
+        self.cgroup_constrained = constrained;
+    }
+
+    /// If `details` is `true`, refreshing a process also reads its `/proc/[pid]/status`,
+    /// `/proc/[pid]/cgroup` and `/proc/[pid]/schedstat` files, as well as the `schedstat` file
+    /// of each of its threads. It is `false` by default: the `status` and `cgroup` files are
+    /// then only read when the process is first seen, and the scheduler statistics are not
+    /// computed.
+    ///
+    /// It concerns [`Process::status_description`][crate::Process#method.status_description],
+    /// the namespace pids (like [`Process::namespace_pids`][crate::Process#method.namespace_pids]),
+    /// [`Process::cgroup`][crate::Process#method.cgroup],
+    /// [`Process::scheduler_stats`][crate::Process#method.scheduler_stats] and
+    /// [`Thread::scheduler_stats`][crate::Thread::scheduler_stats].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.set_process_details(true);
+    /// s.refresh_processes();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{:?}", process.scheduler_stats());
+    /// }
+    /// ```
+    pub fn set_process_details(&mut self, details: bool) {
+        self.process_details = details;
+    }
+
+    /// Returns the memory information read from `/proc/meminfo` by
+    /// [`SystemExt::refresh_memory`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_memory();
+    /// let info = s.get_memory_info();
+    /// println!("{} kB of dirty pages", info.dirty);
+    /// ```
+    pub fn get_memory_info(&self) -> &MemoryInfo {
+        &self.memory_info
+    }
+
+    /// Refreshes the virtual memory statistics, read from `/proc/vmstat`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_vmstat();
+    /// ```
+    pub fn refresh_vmstat(&mut self) {
+        if let Ok(data) = get_all_data("/proc/vmstat", 16_385) {
+            self.vmstat.update(&data);
+        }
+    }
+
+    /// Returns the virtual memory statistics read from `/proc/vmstat` by
+    /// [`System::refresh_vmstat`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_vmstat();
+    /// println!("{} major page faults", s.get_vmstat().get_total_major_page_faults());
+    /// ```
+    pub fn get_vmstat(&self) -> &VmStat {
+        &self.vmstat
+    }
+
+    /// Returns the kernel activity counters read from `/proc/stat` by
+    /// [`SystemExt::refresh_cpu`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let stats = s.get_kernel_stats();
+    /// println!("{} context switches since boot", stats.get_total_context_switches());
+    /// ```
+    pub fn get_kernel_stats(&self) -> &KernelStats {
+        &self.kernel_stats
+    }
+
+    /// Refreshes the interrupts and softirqs counters, read from `/proc/interrupts` and
+    /// `/proc/softirqs`.
+    ///
+    /// The counters are mapped onto the processors, so they should have been retrieved first
+    /// (which is done when creating the [`System`]).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_interrupts();
+    /// ```
+    pub fn refresh_interrupts(&mut self) {
+        if let Ok(data) = get_all_data("/proc/interrupts", 16_384) {
+            self.interrupts = parse_interrupts(&data, &self.processors, &self.interrupts);
+        }
+        if let Ok(data) = get_all_data("/proc/softirqs", 4_096) {
+            self.softirqs = parse_interrupts(&data, &self.processors, &self.softirqs);
+        }
+    }
+
+    /// Returns the interrupts read from `/proc/interrupts` by [`System::refresh_interrupts`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_interrupts();
+    /// for interrupt in s.get_interrupts() {
+    ///     println!(
+    ///         "{} ({}): {:?}",
+    ///         interrupt.get_name(),
+    ///         interrupt.get_device(),
+    ///         interrupt.get_total_counts(),
+    ///     );
+    /// }
+    /// ```
+    pub fn get_interrupts(&self) -> &[Interrupt] {
+        &self.interrupts
+    }
+
+    /// Returns the softirqs read from `/proc/softirqs` by [`System::refresh_interrupts`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_interrupts();
+    /// if let Some(net_rx) = s.get_softirqs().iter().find(|i| i.get_name() == "NET_RX") {
+    ///     println!("{:?}", net_rx.get_total_counts());
+    /// }
+    /// ```
+    pub fn get_softirqs(&self) -> &[Interrupt] {
+        &self.softirqs
+    }
+
+    /// Returns the load average with the number of runnable and total scheduling entities, the
+    /// last pid allocated (read from `/proc/loadavg`) and the maximum pid (read from
+    /// `/proc/sys/kernel/pid_max`).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let load = s.get_detailed_load_average();
+    /// println!("{}% of the pids are used", load.pid_usage());
+    /// ```
+    pub fn get_detailed_load_average(&self) -> DetailedLoadAvg {
+        let pid_max = get_all_data("/proc/sys/kernel/pid_max", 16)
+            .ok()
+            .and_then(|data| data.trim().parse().ok())
+            .unwrap_or(0);
+        get_all_data("/proc/loadavg", 64)
+            .ok()
+            .and_then(|data| parse_loadavg(&data, pid_max))
+            .unwrap_or_default()
+    }
+
+    /// Returns the time spent idle by all the processors (the second value of `/proc/uptime`),
+    /// updated at the same time as [`SystemExt::get_uptime`]. Since it is the sum of the idle
+    /// time of each processor, it can be bigger than the uptime.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let idle = s.get_idle_time().as_secs_f64() / s.get_processors().len() as f64;
+    /// println!("idle {:.1}% of the time since boot", idle * 100. / s.get_uptime() as f64);
+    /// ```
+    pub fn get_idle_time(&self) -> Duration {
+        self.idle_time
+    }
+
+    /// Returns the identifier of the current boot (`/proc/sys/kernel/random/boot_id`). It
+    /// changes every time the system boots.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("boot id: {:?}", s.get_boot_id());
+    /// ```
+    pub fn get_boot_id(&self) -> Option<String> {
+        read_id(Path::new("/proc/sys/kernel/random/boot_id"))
+    }
+
+    /// Returns the identifier of the machine, read from `/etc/machine-id` (or from
+    /// `/var/lib/dbus/machine-id` on older systems). It is set when the system is installed.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("machine id: {:?}", s.get_machine_id());
+    /// ```
+    pub fn get_machine_id(&self) -> Option<String> {
+        read_id(Path::new("/etc/machine-id"))
+            .or_else(|| read_id(Path::new("/var/lib/dbus/machine-id")))
+    }
+
+    /// Refreshes the swap areas, read from `/proc/swaps` (and from `/sys/block/zram*` for the
+    /// zram devices).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_swaps();
+    /// ```
+    pub fn refresh_swaps(&mut self) {
+        self.swaps = get_swaps();
+    }
+
+    /// Returns the swap areas read from `/proc/swaps` by [`System::refresh_swaps`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_swaps();
+    /// for swap in s.get_swaps() {
+    ///     println!("{:?}: {:?} with priority {}", swap.path, swap.swap_type, swap.priority);
+    /// }
+    /// ```
+    pub fn get_swaps(&self) -> &[Swap] {
+        &self.swaps
+    }
+
+    /// Returns the NUMA nodes of the system, sorted by identifier. It is empty if the kernel
+    /// doesn't support NUMA.
+    ///
+    /// This information isn't stored when refreshing, it is read every time this method is
+    /// called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for node in s.get_numa_nodes() {
+    ///     println!("node {}: {} pages allocated on a remote node", node.id, node.stats.miss);
+    /// }
+    /// ```
+    pub fn get_numa_nodes(&self) -> Vec<NumaNode> {
+        get_numa_nodes()
+    }
+
+    /// Returns the processors (from [`SystemExt::get_processors`]) which belong to `node`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for node in s.get_numa_nodes() {
+    ///     let usage = s
+    ///         .get_numa_node_processors(&node)
+    ///         .iter()
+    ///         .map(|p| p.get_cpu_usage())
+    ///         .sum::<f32>();
+    ///     println!("node {}: {}%", node.id, usage);
+    /// }
+    /// ```
+    pub fn get_numa_node_processors(&self, node: &NumaNode) -> Vec<&Processor> {
+        // Processors are named after their identifier ("cpu3"), which isn't always their
+        // position if some CPUs are offline.
+        self.processors
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.name.strip_prefix("cpu").and_then(|id| id.parse::<usize>().ok()),
+                    Some(id) if node.cpus.contains(&id)
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the system-wide Pressure Stall Information of `resource`, read from
+    /// `/proc/pressure`. `None` is returned if it isn't available (before Linux 4.20 or if PSI
+    /// is disabled).
+    ///
+    /// ```no_run
+    /// use sysinfo::{PressureResource, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(pressure) = s.get_pressure(PressureResource::Io) {
+    ///     println!("{:?}", pressure);
+    /// }
+    /// ```
+    pub fn get_pressure(&self, resource: PressureResource) -> Option<Pressure> {
+        get_pressure(None, resource)
+    }
+
+    /// Returns the Pressure Stall Information of `resource` in `cgroup`, a path in the cgroup
+    /// v2 hierarchy such as the one returned by [`Process::cgroup`][crate::Process#method.cgroup].
+    ///
+    /// ```no_run
+    /// use sysinfo::{PressureResource, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{:?}", s.get_cgroup_pressure(process.cgroup(), PressureResource::Cpu));
+    /// }
+    /// ```
+    pub fn get_cgroup_pressure(
+        &self,
+        cgroup: &str,
+        resource: PressureResource,
+    ) -> Option<Pressure> {
+        get_pressure(Some(cgroup), resource)
+    }
+
+    /// Sends the raw `signal` to all the processes of the session `session_id` (see
+    /// [`Process::session_id`][crate::Process#method.session_id]). Returns the number of
+    /// processes the signal was successfully sent to.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     s.kill_session(process.session_id(), libc::SIGHUP);
+    /// }
+    /// ```
+    pub fn kill_session(&self, session_id: Pid, signal: i32) -> usize {
+        self.process_list
+            .tasks
+            .values()
+            .filter(|p| p.session_id == session_id && p.kill_raw(signal))
+            .count()
+    }
+
+    /// Returns the process whose pid is `container_pid` in the PID namespace `pid_namespace`
+    /// (see [`Process::pid_namespace`][crate::Process#method.pid_namespace]). It allows to find
+    /// the process corresponding to a pid seen from inside a container.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// // 1337 is any process running in the container.
+    /// if let Some(namespace) = s.get_process(1337).and_then(|p| p.pid_namespace()) {
+    ///     // The process with the pid 1 inside the container.
+    ///     if let Some(process) = s.get_process_by_container_pid(namespace, 1) {
+    ///         println!("host pid: {}", process.pid());
+    ///     }
+    /// }
+    /// ```
+    pub fn get_process_by_container_pid(
+        &self,
+        pid_namespace: u64,
+        container_pid: Pid,
+    ) -> Option<&Process> {
+        // Only checking the namespace of the processes with the right pid since it requires to
+        // read a file.
+        self.process_list.tasks.values().find(|p| {
+            p.container_pid() == Some(container_pid) && p.pid_namespace() == Some(pid_namespace)
+        })
+    }
+
+    /// Groups the processes by [control group][crate::Process#method.cgroup] and returns the
+    /// resources used by each group. Processes without control group are ignored.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (cgroup, usage) in s.get_process_groups_by_cgroup() {
+    ///     println!("{}: {}%", cgroup, usage.cpu_usage);
+    /// }
+    /// ```
+    pub fn get_process_groups_by_cgroup(&self) -> HashMap<String, ProcessesUsage> {
+        self.get_process_groups(|process| {
+            if process.cgroup.is_empty() {
+                None
+            } else {
+                Some(process.cgroup.clone())
+            }
+        })
+    }
 }
 
 impl SystemExt for System {
     fn new_with_specifics(refreshes: RefreshKind) -> System {
+        let (uptime, idle_time) = get_uptime();
         let mut s = System {
             process_list: Process::new(0, None, 0),
-            mem_total: 0,
-            mem_free: 0,
-            mem_available: 0,
-            mem_buffers: 0,
-            mem_page_cache: 0,
-            mem_slab_reclaimable: 0,
-            swap_total: 0,
-            swap_free: 0,
+            memory_info: MemoryInfo::default(),
+            vmstat: VmStat::default(),
+            swaps: Vec::new(),
+            kernel_stats: KernelStats::default(),
+            interrupts: Vec::new(),
+            softirqs: Vec::new(),
+            cgroup_constrained: false,
+            process_details: false,
+            cgroup_limits: CgroupLimits::default(),
             global_processor: Processor::new_with_values(
                 "",
                 0,
@@ -276,8 +843,10 @@ impl SystemExt for System {
             components: Vec::new(),
             disks: Vec::with_capacity(2),
             networks: Networks::new(),
-            uptime: get_uptime(),
+            uptime,
+            idle_time,
             users: Vec::new(),
+            user_ids: HashMap::new(),
             boot_time: boot_time(),
         };
         if !refreshes.cpu() {
@@ -292,58 +861,43 @@ impl SystemExt for System {
     }
 
     fn refresh_memory(&mut self) {
-        self.uptime = get_uptime();
+        self.refresh_uptime();
         if let Ok(data) = get_all_data("/proc/meminfo", 16_385) {
-            for line in data.split('\n') {
-                let field = match line.split(':').next() {
-                    Some("MemTotal") => &mut self.mem_total,
-                    Some("MemFree") => &mut self.mem_free,
-                    Some("MemAvailable") => &mut self.mem_available,
-                    Some("Buffers") => &mut self.mem_buffers,
-                    Some("Cached") => &mut self.mem_page_cache,
-                    Some("SReclaimable") => &mut self.mem_slab_reclaimable,
-                    Some("SwapTotal") => &mut self.swap_total,
-                    Some("SwapFree") => &mut self.swap_free,
-                    _ => continue,
-                };
-                if let Some(val_str) = line.rsplit(' ').nth(1) {
-                    if let Ok(value) = u64::from_str(val_str) {
-                        // /proc/meminfo reports KiB, though it says "kB". Convert it.
-                        *field = value * 128 / 125;
-                    }
-                }
-            }
+            self.memory_info = parse_meminfo(&data);
+        }
+        if self.cgroup_constrained {
+            self.cgroup_limits.refresh_memory();
         }
     }
 
     fn refresh_cpu(&mut self) {
-        self.uptime = get_uptime();
+        self.refresh_uptime();
         self.refresh_processors(None);
     }
 
     fn refresh_processes(&mut self) {
-        self.uptime = get_uptime();
+        self.refresh_uptime();
         if refresh_procs(
             &mut self.process_list,
             Path::new("/proc"),
             self.page_size_kb,
-            0,
             self.uptime,
             get_secs_since_epoch(),
+            self.process_details,
         ) {
             self.clear_procs();
         }
     }
 
     fn refresh_process(&mut self, pid: Pid) -> bool {
-        self.uptime = get_uptime();
+        self.refresh_uptime();
         let found = match _get_process_data(
             &Path::new("/proc/").join(pid.to_string()),
             &mut self.process_list,
             self.page_size_kb,
-            0,
             self.uptime,
             get_secs_since_epoch(),
+            self.process_details,
         ) {
             Ok((Some(p), pid)) => {
                 self.process_list.tasks.insert(pid, p);
@@ -359,6 +913,9 @@ impl SystemExt for System {
 
             if let Some(p) = self.process_list.tasks.get_mut(&pid) {
                 compute_cpu_usage(p, self.processors.len() as u64, total_time);
+                for thread in p.threads.values_mut() {
+                    compute_thread_cpu_usage(thread, self.processors.len() as u64, total_time);
+                }
             }
         }
         found
@@ -370,6 +927,7 @@ impl SystemExt for System {
 
     fn refresh_users_list(&mut self) {
         self.users = crate::linux::users::get_users_list();
+        self.user_ids = crate::common::get_user_ids(&self.users);
     }
 
     // COMMON PART
@@ -405,36 +963,45 @@ impl SystemExt for System {
     }
 
     fn get_total_memory(&self) -> u64 {
-        self.mem_total
+        match self.get_constrained_memory() {
+            Some((total, _, _)) => total,
+            None => self.memory_info.total,
+        }
     }
 
     fn get_free_memory(&self) -> u64 {
-        self.mem_free
+        match self.get_constrained_memory() {
+            Some((total, used, _)) => total - used,
+            None => self.memory_info.free,
+        }
     }
 
     fn get_available_memory(&self) -> u64 {
-        self.mem_available
+        match self.get_constrained_memory() {
+            Some((_, _, available)) => available,
+            None => self.memory_info.available,
+        }
     }
 
     fn get_used_memory(&self) -> u64 {
-        self.mem_total
-            - self.mem_free
-            - self.mem_buffers
-            - self.mem_page_cache
-            - self.mem_slab_reclaimable
+        if let Some((_, used, _)) = self.get_constrained_memory() {
+            return used;
+        }
+        let info = &self.memory_info;
+        info.total - info.free - info.buffers - info.cached - info.slab_reclaimable
     }
 
     fn get_total_swap(&self) -> u64 {
-        self.swap_total
+        self.memory_info.swap_total
     }
 
     fn get_free_swap(&self) -> u64 {
-        self.swap_free
+        self.memory_info.swap_free
     }
 
     // need to be checked
     fn get_used_swap(&self) -> u64 {
-        self.swap_total - self.swap_free
+        self.memory_info.swap_total - self.memory_info.swap_free
     }
 
     fn get_components(&self) -> &[Component] {
@@ -462,30 +1029,20 @@ impl SystemExt for System {
     }
 
     fn get_load_average(&self) -> LoadAvg {
-        let mut s = String::new();
-        if File::open("/proc/loadavg")
-            .and_then(|mut f| f.read_to_string(&mut s))
-            .is_err()
-        {
-            return LoadAvg::default();
-        }
-        let loads = s
-            .trim()
-            .split(' ')
-            .take(3)
-            .map(|val| val.parse::<f64>().unwrap())
-            .collect::<Vec<f64>>();
-        LoadAvg {
-            one: loads[0],
-            five: loads[1],
-            fifteen: loads[2],
-        }
+        get_all_data("/proc/loadavg", 64)
+            .ok()
+            .and_then(|data| parse_load(&data))
+            .unwrap_or_default()
     }
 
     fn get_users(&self) -> &[User] {
         &self.users
     }
 
+    fn get_user_by_id(&self, user_id: Uid) -> Option<&User> {
+        self.user_ids.get(&user_id).map(|pos| &self.users[*pos])
+    }
+
     #[cfg(not(target_os = "android"))]
     fn get_name(&self) -> Option<String> {
         get_system_info_linux(
@@ -595,9 +1152,9 @@ fn refresh_procs(
     proc_list: &mut Process,
     path: &Path,
     page_size_kb: u64,
-    pid: Pid,
     uptime: u64,
     now: u64,
+    details: bool,
 ) -> bool {
     if let Ok(d) = fs::read_dir(path) {
         let folders = d
@@ -615,8 +1172,8 @@ fn refresh_procs(
                 }
             })
             .collect::<Vec<_>>();
-        if pid == 0 {
-            let proc_list = Wrap(UnsafeCell::new(proc_list));
+        let new_tasks = {
+            let proc_list = Wrap(UnsafeCell::new(&mut *proc_list));
 
             #[cfg(feature = "multithread")]
             use rayon::iter::ParallelIterator;
@@ -627,9 +1184,9 @@ fn refresh_procs(
                         e.as_path(),
                         proc_list.get(),
                         page_size_kb,
-                        pid,
                         uptime,
                         now,
+                        details,
                     ) {
                         p
                     } else {
@@ -637,29 +1194,8 @@ fn refresh_procs(
                     }
                 })
                 .collect::<Vec<_>>()
-        } else {
-            let mut updated_pids = Vec::with_capacity(folders.len());
-            let new_tasks = folders
-                .iter()
-                .filter_map(|e| {
-                    if let Ok((p, pid)) =
-                        _get_process_data(e.as_path(), proc_list, page_size_kb, pid, uptime, now)
-                    {
-                        updated_pids.push(pid);
-                        p
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            // Sub-tasks are not cleaned up outside so we do it here directly.
-            proc_list
-                .tasks
-                .retain(|&pid, _| updated_pids.iter().any(|&x| x == pid));
-            new_tasks
-        }
-        .into_iter()
-        .for_each(|e| {
+        };
+        new_tasks.into_iter().for_each(|e| {
             proc_list.tasks.insert(e.pid(), e);
         });
         true
@@ -668,36 +1204,25 @@ fn refresh_procs(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 fn update_time_and_memory(
     path: &Path,
     entry: &mut Process,
     parts: &[&str],
     page_size_kb: u64,
-    parent_memory: u64,
-    parent_virtual_memory: u64,
-    pid: Pid,
-    uptime: u64,
-    now: u64,
+    details: bool,
 ) {
     {
         // rss
         entry.memory = u64::from_str(parts[23]).unwrap_or(0) * page_size_kb;
-        if entry.memory >= parent_memory {
-            entry.memory -= parent_memory;
-        }
         // vsz
         entry.virtual_memory = u64::from_str(parts[22]).unwrap_or(0);
-        if entry.virtual_memory >= parent_virtual_memory {
-            entry.virtual_memory -= parent_virtual_memory;
-        }
         set_time(
             entry,
             u64::from_str(parts[13]).unwrap_or(0),
             u64::from_str(parts[14]).unwrap_or(0),
         );
     }
-    refresh_procs(entry, &path.join("task"), page_size_kb, pid, uptime, now);
+    refresh_threads(&mut entry.threads, path, details);
 }
 
 macro_rules! unwrap_or_return {
@@ -709,41 +1234,7 @@ macro_rules! unwrap_or_return {
     }};
 }
 
-fn _get_uid_and_gid(status_data: String) -> Option<(uid_t, gid_t)> {
-    // We're only interested in the lines starting with Uid: and Gid:
-    // here. From these lines, we're looking at the second entry to get
-    // the effective u/gid.
-
-    let f = |h: &str, n: &str| -> Option<uid_t> {
-        if h.starts_with(n) {
-            h.split_whitespace().nth(2).unwrap_or("0").parse().ok()
-        } else {
-            None
-        }
-    };
-    let mut uid = None;
-    let mut gid = None;
-    for line in status_data.lines() {
-        if let Some(u) = f(line, "Uid:") {
-            assert!(uid.is_none());
-            uid = Some(u);
-        } else if let Some(g) = f(line, "Gid:") {
-            assert!(gid.is_none());
-            gid = Some(g);
-        } else {
-            continue;
-        }
-        if uid.is_some() && gid.is_some() {
-            break;
-        }
-    }
-    match (uid, gid) {
-        (Some(u), Some(g)) => Some((u, g)),
-        _ => None,
-    }
-}
-
-fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
+pub(crate) fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
     // The stat file is "interesting" to parse, because spaces cannot
     // be used as delimiters. The second field stores the command name
     // surrounded by parentheses. Unfortunately, whitespace and
@@ -861,24 +1352,26 @@ fn _get_process_data(
     path: &Path,
     proc_list: &mut Process,
     page_size_kb: u64,
-    pid: Pid,
     uptime: u64,
     now: u64,
+    details: bool,
 ) -> Result<(Option<Process>, Pid), ()> {
     let nb = match path.file_name().and_then(|x| x.to_str()).map(Pid::from_str) {
-        Some(Ok(nb)) if nb != pid => nb,
+        Some(Ok(nb)) => nb,
         _ => return Err(()),
     };
 
-    let get_status = |p: &mut Process, part: &str| {
-        p.status = part
+    let get_status = |p: &mut Process, parts: &[&str]| {
+        p.status = parts[2]
             .chars()
             .next()
             .map(ProcessStatus::from)
             .unwrap_or_else(|| ProcessStatus::Unknown(0));
+        p.kernel_thread = u32::from_str(parts[8]).unwrap_or(0) & PF_KTHREAD != 0;
+        // The process group and the session can be changed at any time.
+        p.group_id = Pid::from_str(parts[4]).unwrap_or(0);
+        p.session_id = Pid::from_str(parts[5]).unwrap_or(0);
     };
-    let parent_memory = proc_list.memory;
-    let parent_virtual_memory = proc_list.virtual_memory;
     if let Some(ref mut entry) = proc_list.tasks.get_mut(&nb) {
         let data = if let Some(ref mut f) = entry.stat_file {
             get_all_data_from_file(f, 1024).map_err(|_| ())?
@@ -891,19 +1384,14 @@ fn _get_process_data(
             data
         };
         let parts = parse_stat_file(&data)?;
-        get_status(entry, parts[2]);
-        update_time_and_memory(
-            path,
-            entry,
-            &parts,
-            page_size_kb,
-            parent_memory,
-            parent_virtual_memory,
-            nb,
-            uptime,
-            now,
-        );
+        get_status(entry, &parts);
+        update_time_and_memory(path, entry, &parts, page_size_kb, details);
         update_process_disk_activity(entry, path);
+        if details {
+            update_process_status_data(entry, path);
+            update_process_cgroup(entry, path);
+            entry.scheduler_values.update(path);
+        }
         return Ok((None, nb));
     }
 
@@ -916,13 +1404,9 @@ fn _get_process_data(
     let parts = parse_stat_file(&data)?;
     let name = parts[1];
 
-    let parent_pid = if proc_list.pid != 0 {
-        Some(proc_list.pid)
-    } else {
-        match Pid::from_str(parts[3]) {
-            Ok(p) if p != 0 => Some(p),
-            _ => None,
-        }
+    let parent_pid = match Pid::from_str(parts[3]) {
+        Ok(p) if p != 0 => Some(p),
+        _ => None,
     };
 
     let clock_cycle = unsafe { sysconf(_SC_CLK_TCK) } as u64;
@@ -931,64 +1415,39 @@ fn _get_process_data(
     let mut p = Process::new(nb, parent_pid, start_time);
 
     p.stat_file = stat_file;
-    get_status(&mut p, parts[2]);
+    get_status(&mut p, &parts);
+    update_process_status_data(&mut p, path);
 
+    p.name = name.into();
+    tmp.pop();
+    tmp.push("cmdline");
+    p.cmd = copy_from_file(&tmp);
     tmp.pop();
-    tmp.push("status");
-    if let Ok(data) = get_all_data(&tmp, 16_385) {
-        if let Some((uid, gid)) = _get_uid_and_gid(data) {
-            p.uid = uid;
-            p.gid = gid;
+    tmp.push("exe");
+    match tmp.read_link() {
+        Ok(exe_path) => {
+            p.exe = exe_path;
+        }
+        Err(_) => {
+            p.exe = PathBuf::new();
         }
     }
+    tmp.pop();
+    tmp.push("environ");
+    p.environ = copy_from_file(&tmp);
+    tmp.pop();
+    tmp.push("cwd");
+    p.cwd = realpath(&tmp);
+    tmp.pop();
+    tmp.push("root");
+    p.root = realpath(&tmp);
 
-    if proc_list.pid != 0 {
-        // If we're getting information for a child, no need to get those info since we
-        // already have them...
-        p.cmd = proc_list.cmd.clone();
-        p.name = proc_list.name.clone();
-        p.environ = proc_list.environ.clone();
-        p.exe = proc_list.exe.clone();
-        p.cwd = proc_list.cwd.clone();
-        p.root = proc_list.root.clone();
-    } else {
-        p.name = name.into();
-        tmp.pop();
-        tmp.push("cmdline");
-        p.cmd = copy_from_file(&tmp);
-        tmp.pop();
-        tmp.push("exe");
-        match tmp.read_link() {
-            Ok(exe_path) => {
-                p.exe = exe_path;
-            }
-            Err(_) => {
-                p.exe = PathBuf::new();
-            }
-        }
-        tmp.pop();
-        tmp.push("environ");
-        p.environ = copy_from_file(&tmp);
-        tmp.pop();
-        tmp.push("cwd");
-        p.cwd = realpath(&tmp);
-        tmp.pop();
-        tmp.push("root");
-        p.root = realpath(&tmp);
-    }
-
-    update_time_and_memory(
-        path,
-        &mut p,
-        &parts,
-        page_size_kb,
-        proc_list.memory,
-        proc_list.virtual_memory,
-        nb,
-        uptime,
-        now,
-    );
+    update_time_and_memory(path, &mut p, &parts, page_size_kb, details);
     update_process_disk_activity(&mut p, path);
+    update_process_cgroup(&mut p, path);
+    if details {
+        p.scheduler_values.update(path);
+    }
     Ok((Some(p), nb))
 }
 
@@ -1035,13 +1494,37 @@ pub fn get_all_data<P: AsRef<Path>>(file_path: P, size: usize) -> io::Result<Str
     get_all_data_from_file(&mut file, size)
 }
 
-fn get_uptime() -> u64 {
+fn read_id(path: &Path) -> Option<String> {
+    let id = get_all_data(path, 64).ok()?;
+    let id = id.trim();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_owned())
+    }
+}
+
+// Returns the uptime (in seconds) and the time spent idle by all processors.
+fn get_uptime() -> (u64, Duration) {
     let content = get_all_data("/proc/uptime", 50).unwrap_or_default();
-    content
-        .split('.')
+    parse_uptime(&content)
+}
+
+fn parse_uptime(data: &str) -> (u64, Duration) {
+    // The content looks like this: "3164.73 2356.34".
+    let mut parts = data.split_whitespace();
+    let uptime = parts
         .next()
+        .and_then(|t| t.split('.').next())
         .and_then(|t| t.parse().ok())
-        .unwrap_or_default()
+        .unwrap_or_default();
+    let idle_time = parts
+        .next()
+        .and_then(|t| t.parse::<f64>().ok())
+        .filter(|t| *t >= 0.)
+        .map(Duration::from_secs_f64)
+        .unwrap_or_default();
+    (uptime, idle_time)
 }
 
 fn get_secs_since_epoch() -> u64 {
@@ -1057,7 +1540,75 @@ mod test {
     use super::get_system_info_android;
     #[cfg(not(target_os = "android"))]
     use super::get_system_info_linux;
-    use super::InfoType;
+    #[cfg(not(target_os = "android"))]
+    use super::read_id;
+    use super::{parse_uptime, InfoType, System};
+    use crate::sys::cgroup::CgroupLimits;
+    use crate::SystemExt;
+    use std::time::Duration;
+
+    #[test]
+    fn check_parse_uptime() {
+        assert_eq!(
+            parse_uptime("3164.73 2356.34\n"),
+            (3164, Duration::from_millis(2_356_340))
+        );
+        assert_eq!(parse_uptime("12.5"), (12, Duration::default()));
+        assert_eq!(parse_uptime(""), (0, Duration::default()));
+    }
+
+    #[test]
+    fn check_cgroup_limits() {
+        let mut s = System::new();
+        s.memory_info.total = 1000;
+        s.memory_info.free = 800;
+        s.cgroup_limits = CgroupLimits {
+            memory_limit: Some(600),
+            memory_usage: Some(500),
+            memory_reclaimable: Some(100),
+            cpu_quota: Some(0.5),
+        };
+        assert_eq!(s.get_effective_memory_limit(), 600);
+        assert_eq!(s.get_effective_cpu_quota(), 0.5);
+        // The system-wide values are reported until the constrained mode is enabled.
+        assert_eq!(s.get_total_memory(), 1000);
+        assert_eq!(s.get_free_memory(), 800);
+
+        s.set_cgroup_constrained(true);
+        assert_eq!(s.get_total_memory(), 600);
+        assert_eq!(s.get_used_memory(), 500);
+        assert_eq!(s.get_free_memory(), 100);
+        assert_eq!(s.get_available_memory(), 200);
+
+        // A limit above the memory of the system is ignored.
+        s.cgroup_limits.memory_limit = Some(5000);
+        s.cgroup_limits.cpu_quota = Some(1_000_000.);
+        assert_eq!(s.get_effective_memory_limit(), 1000);
+        assert_eq!(s.get_effective_cpu_quota(), s.processors.len() as f64);
+        assert_eq!(s.get_total_memory(), 1000);
+
+        // Without the usage of the control group, the system-wide values are used.
+        s.cgroup_limits.memory_usage = None;
+        assert_eq!(s.get_free_memory(), 800);
+    }
+
+    #[test]
+    // `tempfile` is only a dev-dependency on non-android targets.
+    #[cfg(not(target_os = "android"))]
+    fn check_read_id() {
+        let dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let path = dir.path().join("machine-id");
+
+        assert_eq!(read_id(&path), None);
+        std::fs::write(&path, "\n").expect("failed to write machine-id");
+        assert_eq!(read_id(&path), None);
+        std::fs::write(&path, "4c2f7b1e9d8a4f0b8c3e2a1d0f9e8b7c\n")
+            .expect("failed to write machine-id");
+        assert_eq!(
+            read_id(&path).as_deref(),
+            Some("4c2f7b1e9d8a4f0b8c3e2a1d0f9e8b7c")
+        );
+    }
 
     #[test]
     #[cfg(target_os = "android")]
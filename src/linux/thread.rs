@@ -0,0 +1,186 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use libc::{sysconf, _SC_CLK_TCK};
+
+use crate::sys::process::{ProcessStatus, SchedulerStats, SchedulerValues};
+use crate::sys::system::{get_all_data, parse_stat_file};
+use crate::Pid;
+
+/// Struct containing a thread's information.
+///
+/// Threads are listed by [`Process::threads`][crate::Process#method.threads].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// if let Some(process) = s.get_process(1337) {
+///     for thread in process.threads().values() {
+///         println!("{:?}", thread);
+///     }
+/// }
+/// ```
+pub struct Thread {
+    tid: Pid,
+    name: String,
+    status: ProcessStatus,
+    utime: u64,
+    stime: u64,
+    old_utime: u64,
+    old_stime: u64,
+    cpu_usage: f32,
+    updated: bool,
+    scheduler_values: SchedulerValues,
+}
+
+impl Thread {
+    fn new(tid: Pid) -> Thread {
+        Thread {
+            tid,
+            name: String::new(),
+            status: ProcessStatus::Unknown(0),
+            utime: 0,
+            stime: 0,
+            old_utime: 0,
+            old_stime: 0,
+            cpu_usage: 0.,
+            updated: false,
+            scheduler_values: SchedulerValues::default(),
+        }
+    }
+
+    /// Returns the thread id.
+    pub fn tid(&self) -> Pid {
+        self.tid
+    }
+
+    /// Returns the name of the thread (the `comm` field of `/proc/[pid]/task/[tid]/stat`, which
+    /// is the same as the content of `/proc/[pid]/task/[tid]/comm`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the status of the thread.
+    pub fn status(&self) -> ProcessStatus {
+        self.status
+    }
+
+    /// Returns the CPU usage of the thread (in %), computed the same way as
+    /// [`ProcessExt::cpu_usage`][crate::ProcessExt::cpu_usage].
+    pub fn cpu_usage(&self) -> f32 {
+        self.cpu_usage
+    }
+
+    /// Returns the time spent by the thread in user mode (in milliseconds).
+    pub fn user_time(&self) -> u64 {
+        ticks_to_ms(self.utime)
+    }
+
+    /// Returns the time spent by the thread in kernel mode (in milliseconds).
+    pub fn system_time(&self) -> u64 {
+        ticks_to_ms(self.stime)
+    }
+
+    /// Returns the scheduler statistics of the thread, read from
+    /// `/proc/[pid]/task/[tid]/schedstat` if
+    /// [`System::set_process_details`][crate::System#method.set_process_details] is enabled.
+    pub fn scheduler_stats(&self) -> SchedulerStats {
+        self.scheduler_values.stats()
+    }
+}
+
+impl fmt::Debug for Thread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Thread")
+            .field("tid", &self.tid())
+            .field("name", &self.name())
+            .field("status", &self.status())
+            .field("CPU usage", &self.cpu_usage())
+            .field("user time", &self.user_time())
+            .field("system time", &self.system_time())
+            .finish()
+    }
+}
+
+fn ticks_to_ms(ticks: u64) -> u64 {
+    let clock_cycle = unsafe { sysconf(_SC_CLK_TCK) } as u64;
+    if clock_cycle == 0 {
+        return 0;
+    }
+    ticks * 1_000 / clock_cycle
+}
+
+pub(crate) fn compute_thread_cpu_usage(t: &mut Thread, nb_processors: u64, total_time: f32) {
+    // First time updating the values without reference, wait for a second cycle to update cpu_usage
+    if t.old_utime == 0 && t.old_stime == 0 {
+        return;
+    }
+
+    t.cpu_usage = ((t.utime.saturating_sub(t.old_utime) + t.stime.saturating_sub(t.old_stime))
+        * nb_processors
+        * 100) as f32
+        / total_time;
+}
+
+fn refresh_thread(thread: &mut Thread, path: &Path, details: bool) {
+    let data = match get_all_data(path.join("stat"), 1024) {
+        Ok(data) => data,
+        Err(_) => return,
+    };
+    let parts = match parse_stat_file(&data) {
+        Ok(parts) => parts,
+        Err(_) => return,
+    };
+    // The name in the stat file is the same as the content of the `comm` file, no need to read
+    // another file.
+    if thread.name != parts[1] {
+        thread.name = parts[1].to_owned();
+    }
+    thread.status = parts[2]
+        .chars()
+        .next()
+        .map(ProcessStatus::from)
+        .unwrap_or(ProcessStatus::Unknown(0));
+    thread.old_utime = thread.utime;
+    thread.old_stime = thread.stime;
+    thread.utime = u64::from_str(parts[13]).unwrap_or(0);
+    thread.stime = u64::from_str(parts[14]).unwrap_or(0);
+    if details {
+        thread.scheduler_values.update(path);
+    }
+    thread.updated = true;
+}
+
+/// Refreshes the threads listed in `[path]/task`. Threads which don't exist anymore are removed.
+/// The scheduler statistics are only read if `details` is `true`.
+pub(crate) fn refresh_threads(
+    threads: &mut HashMap<Pid, Thread>,
+    path: &Path,
+    details: bool,
+) -> bool {
+    let entries = match fs::read_dir(path.join("task")) {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    for entry in entries.flatten() {
+        let tid = match entry.file_name().to_str().map(Pid::from_str) {
+            Some(Ok(tid)) => tid,
+            _ => continue,
+        };
+        let thread = threads.entry(tid).or_insert_with(|| Thread::new(tid));
+        refresh_thread(thread, &entry.path(), details);
+    }
+    // We remove the threads which weren't updated and reset the flag for the others.
+    threads.retain(|_, thread| std::mem::replace(&mut thread.updated, false));
+    true
+}
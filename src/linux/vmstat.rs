@@ -0,0 +1,412 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+/// Virtual memory statistics, read from `/proc/vmstat` by
+/// [`System::refresh_vmstat`][crate::System#method.refresh_vmstat]. Each counter is available
+/// since boot (`get_total_*`) and since the previous refresh; the ones the running kernel
+/// doesn't report are `0`.
+///
+/// It is returned by [`System::get_vmstat`][crate::System#method.get_vmstat].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+/// use std::{thread, time::Duration};
+///
+/// let mut s = System::new();
+/// s.refresh_vmstat();
+/// thread::sleep(Duration::from_secs(1));
+/// s.refresh_vmstat();
+/// let vmstat = s.get_vmstat();
+/// println!("swap-in since the last refresh: {} pages", vmstat.get_swapped_in());
+/// println!("OOM kills since boot: {}", vmstat.get_total_oom_kills());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct VmStat {
+    refreshed: bool,
+    paged_in: u64,
+    old_paged_in: u64,
+    paged_out: u64,
+    old_paged_out: u64,
+    swapped_in: u64,
+    old_swapped_in: u64,
+    swapped_out: u64,
+    old_swapped_out: u64,
+    page_faults: u64,
+    old_page_faults: u64,
+    major_page_faults: u64,
+    old_major_page_faults: u64,
+    oom_kills: u64,
+    old_oom_kills: u64,
+    alloc_stalls: u64,
+    old_alloc_stalls: u64,
+    compaction_stalls: u64,
+    old_compaction_stalls: u64,
+    compaction_failures: u64,
+    old_compaction_failures: u64,
+    compaction_successes: u64,
+    old_compaction_successes: u64,
+    thp_fault_allocs: u64,
+    old_thp_fault_allocs: u64,
+    thp_fault_fallbacks: u64,
+    old_thp_fault_fallbacks: u64,
+    thp_collapse_allocs: u64,
+    old_thp_collapse_allocs: u64,
+    thp_splits: u64,
+    old_thp_splits: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    paged_in: u64,
+    paged_out: u64,
+    swapped_in: u64,
+    swapped_out: u64,
+    page_faults: u64,
+    major_page_faults: u64,
+    oom_kills: u64,
+    alloc_stalls: u64,
+    compaction_stalls: u64,
+    compaction_failures: u64,
+    compaction_successes: u64,
+    thp_fault_allocs: u64,
+    thp_fault_fallbacks: u64,
+    thp_collapse_allocs: u64,
+    thp_splits: u64,
+}
+
+fn parse_vmstat(data: &str) -> Counters {
+    let mut counters = Counters::default();
+
+    for line in data.lines() {
+        // A line looks like this: "pgfault 37586925".
+        let mut parts = line.split_whitespace();
+        let (key, value) = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+            (Some(key), Some(value)) => (key, value),
+            _ => continue,
+        };
+        match key {
+            "pgpgin" => counters.paged_in = value,
+            "pgpgout" => counters.paged_out = value,
+            "pswpin" => counters.swapped_in = value,
+            "pswpout" => counters.swapped_out = value,
+            "pgfault" => counters.page_faults = value,
+            "pgmajfault" => counters.major_page_faults = value,
+            "oom_kill" => counters.oom_kills = value,
+            "compact_stall" => counters.compaction_stalls = value,
+            "compact_fail" => counters.compaction_failures = value,
+            "compact_success" => counters.compaction_successes = value,
+            "thp_fault_alloc" => counters.thp_fault_allocs = value,
+            "thp_fault_fallback" => counters.thp_fault_fallbacks = value,
+            "thp_collapse_alloc" => counters.thp_collapse_allocs = value,
+            "thp_split_page" => counters.thp_splits = value,
+            // Since Linux 4.8, there is one counter per memory zone ("allocstall_normal",
+            // "allocstall_movable", etc) instead of "allocstall".
+            key if key.starts_with("allocstall") => counters.alloc_stalls += value,
+            _ => {}
+        }
+    }
+    counters
+}
+
+impl VmStat {
+    pub(crate) fn update(&mut self, data: &str) {
+        let counters = parse_vmstat(data);
+        let first = !self.refreshed;
+
+        old_and_new!(self, paged_in, old_paged_in, counters.paged_in, first);
+        old_and_new!(self, paged_out, old_paged_out, counters.paged_out, first);
+        old_and_new!(self, swapped_in, old_swapped_in, counters.swapped_in, first);
+        old_and_new!(
+            self,
+            swapped_out,
+            old_swapped_out,
+            counters.swapped_out,
+            first
+        );
+        old_and_new!(
+            self,
+            page_faults,
+            old_page_faults,
+            counters.page_faults,
+            first
+        );
+        old_and_new!(
+            self,
+            major_page_faults,
+            old_major_page_faults,
+            counters.major_page_faults,
+            first
+        );
+        old_and_new!(self, oom_kills, old_oom_kills, counters.oom_kills, first);
+        old_and_new!(
+            self,
+            alloc_stalls,
+            old_alloc_stalls,
+            counters.alloc_stalls,
+            first
+        );
+        old_and_new!(
+            self,
+            compaction_stalls,
+            old_compaction_stalls,
+            counters.compaction_stalls,
+            first
+        );
+        old_and_new!(
+            self,
+            compaction_failures,
+            old_compaction_failures,
+            counters.compaction_failures,
+            first
+        );
+        old_and_new!(
+            self,
+            compaction_successes,
+            old_compaction_successes,
+            counters.compaction_successes,
+            first
+        );
+        old_and_new!(
+            self,
+            thp_fault_allocs,
+            old_thp_fault_allocs,
+            counters.thp_fault_allocs,
+            first
+        );
+        old_and_new!(
+            self,
+            thp_fault_fallbacks,
+            old_thp_fault_fallbacks,
+            counters.thp_fault_fallbacks,
+            first
+        );
+        old_and_new!(
+            self,
+            thp_collapse_allocs,
+            old_thp_collapse_allocs,
+            counters.thp_collapse_allocs,
+            first
+        );
+        old_and_new!(self, thp_splits, old_thp_splits, counters.thp_splits, first);
+        self.refreshed = true;
+    }
+
+    /// Returns the number of kB paged in from the disk since the last refresh (`pgpgin`).
+    pub fn get_paged_in(&self) -> u64 {
+        self.paged_in.saturating_sub(self.old_paged_in)
+    }
+
+    /// Returns the number of kB paged in from the disk since boot (`pgpgin`).
+    pub fn get_total_paged_in(&self) -> u64 {
+        self.paged_in
+    }
+
+    /// Returns the number of kB paged out to the disk since the last refresh (`pgpgout`).
+    pub fn get_paged_out(&self) -> u64 {
+        self.paged_out.saturating_sub(self.old_paged_out)
+    }
+
+    /// Returns the number of kB paged out to the disk since boot (`pgpgout`).
+    pub fn get_total_paged_out(&self) -> u64 {
+        self.paged_out
+    }
+
+    /// Returns the number of pages swapped in since the last refresh (`pswpin`).
+    pub fn get_swapped_in(&self) -> u64 {
+        self.swapped_in.saturating_sub(self.old_swapped_in)
+    }
+
+    /// Returns the number of pages swapped in since boot (`pswpin`).
+    pub fn get_total_swapped_in(&self) -> u64 {
+        self.swapped_in
+    }
+
+    /// Returns the number of pages swapped out since the last refresh (`pswpout`).
+    pub fn get_swapped_out(&self) -> u64 {
+        self.swapped_out.saturating_sub(self.old_swapped_out)
+    }
+
+    /// Returns the number of pages swapped out since boot (`pswpout`).
+    pub fn get_total_swapped_out(&self) -> u64 {
+        self.swapped_out
+    }
+
+    /// Returns the number of page faults since the last refresh (`pgfault`).
+    pub fn get_page_faults(&self) -> u64 {
+        self.page_faults.saturating_sub(self.old_page_faults)
+    }
+
+    /// Returns the number of page faults since boot (`pgfault`).
+    pub fn get_total_page_faults(&self) -> u64 {
+        self.page_faults
+    }
+
+    /// Returns the number of page faults which required I/O since the last refresh
+    /// (`pgmajfault`).
+    pub fn get_major_page_faults(&self) -> u64 {
+        self.major_page_faults
+            .saturating_sub(self.old_major_page_faults)
+    }
+
+    /// Returns the number of page faults which required I/O since boot (`pgmajfault`).
+    pub fn get_total_major_page_faults(&self) -> u64 {
+        self.major_page_faults
+    }
+
+    /// Returns the number of processes killed by the OOM killer since the last refresh
+    /// (`oom_kill`, Linux 4.13 and later).
+    pub fn get_oom_kills(&self) -> u64 {
+        self.oom_kills.saturating_sub(self.old_oom_kills)
+    }
+
+    /// Returns the number of processes killed by the OOM killer since boot (`oom_kill`, Linux
+    /// 4.13 and later).
+    pub fn get_total_oom_kills(&self) -> u64 {
+        self.oom_kills
+    }
+
+    /// Returns the number of times an allocation had to wait for direct reclaim since the last
+    /// refresh (sum of the `allocstall*` counters).
+    pub fn get_alloc_stalls(&self) -> u64 {
+        self.alloc_stalls.saturating_sub(self.old_alloc_stalls)
+    }
+
+    /// Returns the number of times an allocation had to wait for direct reclaim since boot (sum
+    /// of the `allocstall*` counters).
+    pub fn get_total_alloc_stalls(&self) -> u64 {
+        self.alloc_stalls
+    }
+
+    /// Returns the number of times an allocation had to wait for memory compaction since the
+    /// last refresh (`compact_stall`).
+    pub fn get_compaction_stalls(&self) -> u64 {
+        self.compaction_stalls
+            .saturating_sub(self.old_compaction_stalls)
+    }
+
+    /// Returns the number of times an allocation had to wait for memory compaction since boot
+    /// (`compact_stall`).
+    pub fn get_total_compaction_stalls(&self) -> u64 {
+        self.compaction_stalls
+    }
+
+    /// Returns the number of failed memory compactions since the last refresh
+    /// (`compact_fail`).
+    pub fn get_compaction_failures(&self) -> u64 {
+        self.compaction_failures
+            .saturating_sub(self.old_compaction_failures)
+    }
+
+    /// Returns the number of failed memory compactions since boot (`compact_fail`).
+    pub fn get_total_compaction_failures(&self) -> u64 {
+        self.compaction_failures
+    }
+
+    /// Returns the number of successful memory compactions since the last refresh
+    /// (`compact_success`).
+    pub fn get_compaction_successes(&self) -> u64 {
+        self.compaction_successes
+            .saturating_sub(self.old_compaction_successes)
+    }
+
+    /// Returns the number of successful memory compactions since boot (`compact_success`).
+    pub fn get_total_compaction_successes(&self) -> u64 {
+        self.compaction_successes
+    }
+
+    /// Returns the number of transparent huge pages allocated on page fault since the last
+    /// refresh (`thp_fault_alloc`).
+    pub fn get_thp_fault_allocs(&self) -> u64 {
+        self.thp_fault_allocs
+            .saturating_sub(self.old_thp_fault_allocs)
+    }
+
+    /// Returns the number of transparent huge pages allocated on page fault since boot
+    /// (`thp_fault_alloc`).
+    pub fn get_total_thp_fault_allocs(&self) -> u64 {
+        self.thp_fault_allocs
+    }
+
+    /// Returns the number of page faults which fell back to regular pages because no
+    /// transparent huge page could be allocated since the last refresh (`thp_fault_fallback`).
+    pub fn get_thp_fault_fallbacks(&self) -> u64 {
+        self.thp_fault_fallbacks
+            .saturating_sub(self.old_thp_fault_fallbacks)
+    }
+
+    /// Returns the number of page faults which fell back to regular pages because no
+    /// transparent huge page could be allocated since boot (`thp_fault_fallback`).
+    pub fn get_total_thp_fault_fallbacks(&self) -> u64 {
+        self.thp_fault_fallbacks
+    }
+
+    /// Returns the number of transparent huge pages allocated by `khugepaged` to collapse
+    /// regular pages since the last refresh (`thp_collapse_alloc`).
+    pub fn get_thp_collapse_allocs(&self) -> u64 {
+        self.thp_collapse_allocs
+            .saturating_sub(self.old_thp_collapse_allocs)
+    }
+
+    /// Returns the number of transparent huge pages allocated by `khugepaged` to collapse
+    /// regular pages since boot (`thp_collapse_alloc`).
+    pub fn get_total_thp_collapse_allocs(&self) -> u64 {
+        self.thp_collapse_allocs
+    }
+
+    /// Returns the number of transparent huge pages split into regular pages since the last
+    /// refresh (`thp_split_page`).
+    pub fn get_thp_splits(&self) -> u64 {
+        self.thp_splits.saturating_sub(self.old_thp_splits)
+    }
+
+    /// Returns the number of transparent huge pages split into regular pages since boot
+    /// (`thp_split_page`).
+    pub fn get_total_thp_splits(&self) -> u64 {
+        self.thp_splits
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::VmStat;
+
+    #[test]
+    fn check_vmstat_update() {
+        let mut vmstat = VmStat::default();
+        vmstat.update(
+            "\
+nr_free_pages 829839
+pgpgin 1075518
+pswpin 10
+pgfault 37586925
+oom_kill 1
+allocstall_dma 0
+allocstall_normal 3
+allocstall_movable 4
+thp_split_page 2
+thp_split_page_failed 7
+invalid
+",
+        );
+        // The first refresh doesn't give any difference.
+        assert_eq!(vmstat.get_total_paged_in(), 1075518);
+        assert_eq!(vmstat.get_paged_in(), 0);
+        assert_eq!(vmstat.get_total_swapped_in(), 10);
+        assert_eq!(vmstat.get_total_alloc_stalls(), 7);
+        assert_eq!(vmstat.get_total_thp_splits(), 2);
+        assert_eq!(vmstat.get_total_paged_out(), 0);
+
+        vmstat.update("pgpgin 1075600\npswpin 15\noom_kill 3\nallocstall 9\n");
+        assert_eq!(vmstat.get_paged_in(), 82);
+        assert_eq!(vmstat.get_swapped_in(), 5);
+        assert_eq!(vmstat.get_oom_kills(), 2);
+        assert_eq!(vmstat.get_total_oom_kills(), 3);
+        assert_eq!(vmstat.get_alloc_stalls(), 2);
+        // Counters which disappeared don't underflow.
+        assert_eq!(vmstat.get_page_faults(), 0);
+        assert_eq!(vmstat.get_total_page_faults(), 0);
+    }
+}
@@ -0,0 +1,292 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::{Pid, Process, ProcessExt, ProcessStatus, Uid};
+
+use std::collections::hash_map::Values;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+enum Filter {
+    All,
+    Name(String),
+    Exe(PathBuf),
+    Cmd(String),
+    #[cfg(feature = "regex")]
+    CmdRegex(regex::Regex),
+    Arg(String),
+    Uid(Uid),
+    Parent(Pid),
+    Status(ProcessStatus),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Cgroup(String),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    fn matches(&self, process: &Process) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Name(name) => process.name().contains(name.as_str()),
+            Filter::Exe(exe) => process.exe() == exe,
+            Filter::Cmd(pattern) => glob_match(pattern, &process.cmd().join(" ")),
+            #[cfg(feature = "regex")]
+            Filter::CmdRegex(regex) => regex.is_match(&process.cmd().join(" ")),
+            Filter::Arg(arg) => process.cmd().iter().skip(1).any(|a| a == arg),
+            Filter::Uid(uid) => process.user_id() == Some(*uid),
+            Filter::Parent(parent) => process.parent() == Some(*parent),
+            Filter::Status(status) => process.status() == *status,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Filter::Cgroup(pattern) => glob_match(pattern, process.cgroup()),
+            Filter::And(a, b) => a.matches(process) && b.matches(process),
+            Filter::Or(a, b) => a.matches(process) || b.matches(process),
+            Filter::Not(f) => !f.matches(process),
+        }
+    }
+}
+
+/// A query used to find processes matching some conditions.
+///
+/// Conditions added with the builder methods must all be fulfilled. Use [`ProcessQuery::or`]
+/// and the `!` operator to build other boolean combinations. A query without conditions matches
+/// all processes.
+///
+/// The processes are then retrieved with [`SystemExt::find_processes`][crate::SystemExt::find_processes].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, ProcessQuery, System, SystemExt};
+///
+/// let s = System::new_all();
+/// // We look for the postgres "postmaster", the process started by init.
+/// let query = ProcessQuery::new().name("postgres").arg("-D").parent(1);
+/// for process in s.find_processes(&query) {
+///     println!("{} {:?}", process.pid(), process.cmd());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProcessQuery {
+    filter: Filter,
+}
+
+impl Default for ProcessQuery {
+    fn default() -> ProcessQuery {
+        ProcessQuery::new()
+    }
+}
+
+impl ProcessQuery {
+    /// Creates a query matching all processes.
+    pub fn new() -> ProcessQuery {
+        ProcessQuery {
+            filter: Filter::All,
+        }
+    }
+
+    fn with(self, filter: Filter) -> ProcessQuery {
+        let filter = match self.filter {
+            Filter::All => filter,
+            current => Filter::And(Box::new(current), Box::new(filter)),
+        };
+        ProcessQuery { filter }
+    }
+
+    /// The process [name][ProcessExt::name] must contain `name` (just like
+    /// [`SystemExt::get_process_by_name`][crate::SystemExt::get_process_by_name]).
+    pub fn name(self, name: &str) -> ProcessQuery {
+        self.with(Filter::Name(name.to_owned()))
+    }
+
+    /// The process [executable path][ProcessExt::exe] must be `exe`.
+    pub fn exe<P: AsRef<Path>>(self, exe: P) -> ProcessQuery {
+        self.with(Filter::Exe(exe.as_ref().to_path_buf()))
+    }
+
+    /// The process [command line][ProcessExt::cmd] (its arguments joined with spaces) must match
+    /// the glob `pattern`. `*` matches any sequence of characters and `?` matches any character.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessQuery, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let query = ProcessQuery::new().cmd("*python* -m http.server*");
+    /// println!("{} HTTP servers", s.find_processes(&query).count());
+    /// ```
+    pub fn cmd(self, pattern: &str) -> ProcessQuery {
+        self.with(Filter::Cmd(pattern.to_owned()))
+    }
+
+    /// The process [command line][ProcessExt::cmd] (its arguments joined with spaces) must match
+    /// `regex`.
+    ///
+    /// This method is only available with the `regex` feature.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessQuery, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let regex = regex::Regex::new(r"java .*-jar \S+\.jar").unwrap();
+    /// println!("{} java apps", s.find_processes(&ProcessQuery::new().cmd_regex(regex)).count());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn cmd_regex(self, regex: regex::Regex) -> ProcessQuery {
+        self.with(Filter::CmdRegex(regex))
+    }
+
+    /// One of the process arguments (the first element of [`ProcessExt::cmd`] excluded) must be
+    /// equal to `arg`.
+    pub fn arg(self, arg: &str) -> ProcessQuery {
+        self.with(Filter::Arg(arg.to_owned()))
+    }
+
+    /// The process must be run by the user `uid`. Processes for which the user isn't known (see
+    /// [`ProcessExt::user_id`]) never match.
+    pub fn uid(self, uid: Uid) -> ProcessQuery {
+        self.with(Filter::Uid(uid))
+    }
+
+    /// The process [parent][ProcessExt::parent] must be `parent`.
+    pub fn parent(self, parent: Pid) -> ProcessQuery {
+        self.with(Filter::Parent(parent))
+    }
+
+    /// The process [status][ProcessExt::status] must be `status`.
+    pub fn status(self, status: ProcessStatus) -> ProcessQuery {
+        self.with(Filter::Status(status))
+    }
+
+    /// The process [control group][crate::Process#method.cgroup] must match the glob `pattern`
+    /// (see [`ProcessQuery::cmd`] for the syntax).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessQuery, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let query = ProcessQuery::new().cgroup("/system.slice/postgresql*.service");
+    /// println!("{} postgres processes", s.find_processes(&query).count());
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn cgroup(self, pattern: &str) -> ProcessQuery {
+        self.with(Filter::Cgroup(pattern.to_owned()))
+    }
+
+    /// Processes must match this query and `other`.
+    pub fn and(self, other: ProcessQuery) -> ProcessQuery {
+        self.with(other.filter)
+    }
+
+    /// Processes must match this query or `other`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessQuery, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let query = ProcessQuery::new()
+    ///     .name("postgres")
+    ///     .or(ProcessQuery::new().name("mysqld"));
+    /// // All the databases not started by init.
+    /// let query = query.and(!ProcessQuery::new().parent(1));
+    /// for process in s.find_processes(&query) {
+    ///     println!("{:?}", process);
+    /// }
+    /// ```
+    pub fn or(self, other: ProcessQuery) -> ProcessQuery {
+        ProcessQuery {
+            filter: Filter::Or(Box::new(self.filter), Box::new(other.filter)),
+        }
+    }
+
+    /// Returns `true` if `process` matches this query.
+    pub fn matches(&self, process: &Process) -> bool {
+        self.filter.matches(process)
+    }
+}
+
+impl Not for ProcessQuery {
+    type Output = ProcessQuery;
+
+    /// Processes must not match this query.
+    fn not(self) -> ProcessQuery {
+        ProcessQuery {
+            filter: Filter::Not(Box::new(self.filter)),
+        }
+    }
+}
+
+/// Iterator over the processes matching a [`ProcessQuery`].
+///
+/// It is returned by [`SystemExt::find_processes`][crate::SystemExt::find_processes].
+pub struct ProcessQueryIter<'a> {
+    processes: Values<'a, Pid, Process>,
+    query: &'a ProcessQuery,
+}
+
+impl<'a> ProcessQueryIter<'a> {
+    pub(crate) fn new(
+        processes: Values<'a, Pid, Process>,
+        query: &'a ProcessQuery,
+    ) -> ProcessQueryIter<'a> {
+        ProcessQueryIter { processes, query }
+    }
+}
+
+impl<'a> Iterator for ProcessQueryIter<'a> {
+    type Item = &'a Process;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let query = self.query;
+        self.processes.find(|process| query.matches(process))
+    }
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern and of the text when it was reached, so we can
+    // backtrack if what follows doesn't match.
+    let mut star = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // The `*` matches one more character.
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::glob_match;
+
+    #[test]
+    fn check_glob_match() {
+        assert!(glob_match("", ""));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "postgres -D /var/lib/postgres"));
+        assert!(glob_match("postgres*", "postgres -D /var/lib/postgres"));
+        assert!(glob_match("*-D /var/*", "postgres -D /var/lib/postgres"));
+        assert!(glob_match("p?stgres", "postgres"));
+        assert!(glob_match("*a*b*c", "aXbXbYc"));
+        assert!(!glob_match("postgres", "postgres -D"));
+        assert!(!glob_match("*-X*", "postgres -D"));
+        assert!(!glob_match("?", ""));
+        assert!(!glob_match("a*b", "aXbXc"));
+    }
+}
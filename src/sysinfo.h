@@ -11,6 +11,9 @@
 
 typedef void* CSystem;
 typedef const void* CProcess;
+#ifdef __linux__
+typedef const void* CThread;
+#endif
 typedef const char* RString;
 
 CSystem    *sysinfo_init();
@@ -35,8 +38,10 @@ void        sysinfo_get_processors_usage(CSystem system, unsigned int *length, f
 size_t      sysinfo_get_processes(CSystem system, bool (*fn_pointer)(pid_t, CProcess, void*),
                                   void *data);
 #ifdef __linux__
-size_t      sysinfo_process_get_tasks(CProcess process, bool (*fn_pointer)(pid_t, CProcess, void*),
-                                      void *data);
+size_t      sysinfo_process_get_threads(CProcess process, bool (*fn_pointer)(pid_t, CThread, void*),
+                                        void *data);
+RString     sysinfo_thread_get_name(CThread thread);
+float       sysinfo_thread_get_cpu_usage(CThread thread);
 #endif
 CProcess    sysinfo_get_process_by_pid(CSystem system, pid_t pid);
 pid_t       sysinfo_process_get_pid(CProcess process);
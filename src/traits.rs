@@ -9,13 +9,15 @@ use crate::{
     sys::{Component, Disk, Networks, Process, Processor},
 };
 use crate::{
-    DiskType, DiskUsage, LoadAvg, NetworksIter, Pid, ProcessStatus, RefreshKind, Signal, User,
+    DiskType, DiskUsage, LoadAvg, NetworksIter, Pid, ProcessQuery, ProcessQueryIter,
+    ProcessSortKey, ProcessStatus, ProcessSummary, ProcessesUsage, RefreshKind, Signal, User,
 };
 
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fmt::Debug;
-use std::path::Path;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
 
 /// Contains all the methods of the [`Disk`][crate::Disk] struct.
 ///
@@ -323,6 +325,35 @@ pub trait ProcessExt: Debug {
     /// }
     /// ```
     fn disk_usage(&self) -> DiskUsage;
+
+    /// Returns the id of the user running the process, or `None` if it cannot be retrieved on
+    /// this platform.
+    ///
+    /// A process doesn't keep a reference to the [`System`][crate::System] it comes from, so it cannot return
+    /// its [`User`] directly: use [`SystemExt::get_process_user`] instead.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("User id: {:?}", process.user_id());
+    /// }
+    /// ```
+    fn user_id(&self) -> Option<Uid>;
+
+    /// Returns the number of threads of the process, or `None` if it cannot be retrieved on this
+    /// platform.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     println!("{:?} threads", process.thread_count());
+    /// }
+    /// ```
+    fn thread_count(&self) -> Option<usize>;
 }
 
 /// Contains all the methods of the [`Processor`][crate::Processor] struct.
@@ -716,6 +747,21 @@ pub trait SystemExt: Sized + Debug + Default {
         ret
     }
 
+    /// Returns an iterator over the processes matching `query`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, ProcessQuery, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let query = ProcessQuery::new().exe("/usr/bin/postgres").parent(1);
+    /// if let Some(process) = s.find_processes(&query).next() {
+    ///     println!("postmaster: {}", process.pid());
+    /// }
+    /// ```
+    fn find_processes<'a>(&'a self, query: &'a ProcessQuery) -> ProcessQueryIter<'a> {
+        ProcessQueryIter::new(self.get_processes().values(), query)
+    }
+
     /// Returns "global" processors information (aka the addition of all the processors).
     ///
     /// ```no_run
@@ -882,6 +928,141 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn get_users(&self) -> &[User];
 
+    /// Returns the user with the given user id or `None` if no such user exists.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt, UserExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     if let Some(user) = process.user_id().and_then(|uid| s.get_user_by_id(uid)) {
+    ///         println!("{} is run by {}", process.name(), user.get_name());
+    ///     }
+    /// }
+    /// ```
+    fn get_user_by_id(&self, user_id: Uid) -> Option<&User>;
+
+    /// Returns the user running `process` or `None` if it cannot be retrieved.
+    ///
+    /// Users are only retrieved when calling [`SystemExt::refresh_users_list`] (or when creating
+    /// the [`System`][crate::System] with [`SystemExt::new_all`]).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt, UserExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(1337) {
+    ///     if let Some(user) = s.get_process_user(process) {
+    ///         println!("{} is run by {}", process.name(), user.get_name());
+    ///     }
+    /// }
+    /// ```
+    fn get_process_user(&self, process: &Process) -> Option<&User> {
+        process.user_id().and_then(|uid| self.get_user_by_id(uid))
+    }
+
+    /// Returns the `n` first processes according to `by`, in descending order.
+    ///
+    /// Only the `n` best processes are kept while going through the processes list instead of
+    /// sorting all of them. The returned [`ProcessSummary`] values are owned so they can be kept
+    /// after the next refresh or sent to another thread.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessSortKey, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.top_processes(ProcessSortKey::CpuUsage, 10) {
+    ///     println!("{} {}: {}%", process.pid, process.name, process.cpu_usage);
+    /// }
+    /// ```
+    fn top_processes(&self, by: ProcessSortKey, n: usize) -> Vec<ProcessSummary> {
+        crate::common::top_processes(self.get_processes().values(), by, n)
+    }
+
+    /// Returns the resources used by the processes of each user, indexed by user id.
+    ///
+    /// Processes for which the owner is unknown (see [`ProcessExt::user_id`]) are ignored.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt, UserExt};
+    ///
+    /// let s = System::new_all();
+    /// let mut usages = s.get_users_usage().into_iter().collect::<Vec<_>>();
+    /// // We sort the users by CPU usage to get the "top users".
+    /// usages.sort_by(|(_, a), (_, b)| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
+    /// for (uid, usage) in usages.iter().take(5) {
+    ///     let name = s.get_user_by_id(*uid).map(|u| u.get_name()).unwrap_or("?");
+    ///     println!("{}: {}% ({} processes)", name, usage.cpu_usage, usage.nb_processes);
+    /// }
+    /// ```
+    fn get_users_usage(&self) -> HashMap<Uid, ProcessesUsage> {
+        self.get_process_groups(|process| process.user_id())
+    }
+
+    /// Groups the processes using the key returned by `key` and returns the resources used by
+    /// each group. Processes for which `key` returns `None` are ignored.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// // We group the processes by their parent.
+    /// for (parent, usage) in s.get_process_groups(|process| process.parent()) {
+    ///     println!("{}: {} processes, {}%", parent, usage.nb_processes, usage.cpu_usage);
+    /// }
+    /// ```
+    fn get_process_groups<K, F>(&self, mut key: F) -> HashMap<K, ProcessesUsage>
+    where
+        K: Eq + Hash,
+        F: FnMut(&Process) -> Option<K>,
+    {
+        let mut groups: HashMap<K, ProcessesUsage> = HashMap::new();
+
+        for process in self.get_processes().values() {
+            if let Some(k) = key(process) {
+                groups.entry(k).or_default().add(process);
+            }
+        }
+        groups
+    }
+
+    /// Groups the processes by [name][ProcessExt::name] and returns the resources used by each
+    /// group.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(usage) = s.get_process_groups_by_name().get("firefox") {
+    ///     println!("firefox: {} processes, {} kB", usage.nb_processes, usage.memory);
+    /// }
+    /// ```
+    fn get_process_groups_by_name(&self) -> HashMap<String, ProcessesUsage> {
+        self.get_process_groups(|process| Some(process.name().to_owned()))
+    }
+
+    /// Groups the processes by [executable path][ProcessExt::exe] and returns the resources used
+    /// by each group. Processes without executable path are ignored.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (exe, usage) in s.get_process_groups_by_exe() {
+    ///     println!("{}: {} processes", exe.display(), usage.nb_processes);
+    /// }
+    /// ```
+    fn get_process_groups_by_exe(&self) -> HashMap<PathBuf, ProcessesUsage> {
+        self.get_process_groups(|process| {
+            let exe = process.exe();
+            if exe.as_os_str().is_empty() {
+                None
+            } else {
+                Some(exe.to_path_buf())
+            }
+        })
+    }
+
     /// Returns the disks list.
     ///
     /// ```no_run
@@ -4,12 +4,12 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, Signal, Uid};
 
 use std::path::Path;
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ProcessStatus;
 
 /// Struct containing a process' information.
@@ -83,4 +83,12 @@ impl ProcessExt for Process {
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage::default()
     }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn thread_count(&self) -> Option<usize> {
+        None
+    }
 }
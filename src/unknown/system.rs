@@ -11,6 +11,7 @@ use sys::Disk;
 use sys::Networks;
 use LoadAvg;
 use Pid;
+use Uid;
 use User;
 use {RefreshKind, SystemExt};
 
@@ -144,6 +145,10 @@ impl SystemExt for System {
         &[]
     }
 
+    fn get_user_by_id(&self, _user_id: Uid) -> Option<&User> {
+        None
+    }
+
     fn get_name(&self) -> Option<String> {
         None
     }
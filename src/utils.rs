@@ -123,3 +123,8 @@ where
 {
     val.into_iter()
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn timeval_to_duration(t: libc::timeval) -> std::time::Duration {
+    std::time::Duration::new(t.tv_sec as u64, t.tv_usec as u32 * 1_000)
+}
@@ -4,7 +4,7 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::{DiskUsage, Pid, ProcessExt, Signal, Uid};
 
 use std::fmt::{self, Debug};
 use std::mem::{size_of, zeroed, MaybeUninit};
@@ -42,7 +42,7 @@ use winapi::um::winnt::{
 };
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProcessStatus {
     /// Currently runnable.
     Run,
@@ -435,6 +435,14 @@ impl ProcessExt for Process {
             total_read_bytes: self.read_bytes,
         }
     }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn thread_count(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl Drop for Process {
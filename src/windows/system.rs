@@ -4,7 +4,7 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::{LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
+use crate::{LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, Uid, User};
 use winapi::um::winreg::HKEY_LOCAL_MACHINE;
 
 use crate::sys::component::{self, Component};
@@ -59,6 +59,7 @@ pub struct System {
     networks: Networks,
     boot_time: u64,
     users: Vec<User>,
+    user_ids: HashMap<Uid, usize>,
 }
 
 // Useful for parallel iterations.
@@ -95,6 +96,7 @@ impl SystemExt for System {
             networks: Networks::new(),
             boot_time: unsafe { boot_time() },
             users: Vec::new(),
+            user_ids: HashMap::new(),
         };
         // TODO: in case a translation fails, it might be nice to log it somewhere...
         if let Some(ref mut query) = s.query {
@@ -307,6 +309,7 @@ impl SystemExt for System {
 
     fn refresh_users_list(&mut self) {
         self.users = unsafe { get_users() };
+        self.user_ids = crate::common::get_user_ids(&self.users);
     }
 
     fn get_processes(&self) -> &HashMap<Pid, Process> {
@@ -378,6 +381,10 @@ impl SystemExt for System {
         &self.users
     }
 
+    fn get_user_by_id(&self, user_id: Uid) -> Option<&User> {
+        self.user_ids.get(&user_id).map(|pos| &self.users[*pos])
+    }
+
     fn get_networks(&self) -> &Networks {
         &self.networks
     }
@@ -131,3 +131,510 @@ fn cpu_usage_is_not_nan() {
     });
     assert!(checked > 0);
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_status_description_and_kernel_thread() {
+    use sysinfo::{get_current_pid, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let p = s
+        .get_process(get_current_pid().expect("failed to get current pid"))
+        .expect("failed to get process");
+    assert!(!p.status_description().is_empty());
+    assert!(!p.is_kernel_thread());
+    // The kernel threads are all children of `kthreadd`, which is always PID 2 when visible.
+    if let Some(kthreadd) = s.get_process(2) {
+        if kthreadd.name() == "kthreadd" {
+            assert!(kthreadd.is_kernel_thread());
+        }
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_memory_maps() {
+    use sysinfo::{get_current_pid, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    let pid = get_current_pid().expect("failed to get current pid");
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get process");
+    let maps = p.memory_maps();
+    assert!(!maps.is_empty());
+    assert!(maps.iter().any(|m| m.path() == Some(p.exe())));
+    assert!(p.loaded_libraries().iter().all(|l| l.path().is_some()));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_memory_details() {
+    use sysinfo::{get_current_pid, ProcessExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    let pid = get_current_pid().expect("failed to get current pid");
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get process");
+    let details = p.memory_details();
+    assert!(details.peak_memory > 0);
+    assert!(details.peak_virtual_memory >= details.peak_memory);
+    assert!(details.stack > 0);
+    assert!(details.resident_anonymous + details.resident_file <= details.peak_memory);
+    assert!(p.memory() > 0);
+
+    let nodes = s.get_numa_nodes();
+    // The map is empty if NUMA isn't supported by the kernel.
+    if !nodes.is_empty() {
+        let memory = p.numa_memory();
+        assert!(!memory.is_empty());
+        assert!(memory.keys().all(|id| nodes.iter().any(|n| n.id == *id)));
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_scheduler_stats() {
+    use sysinfo::{get_current_pid, SystemExt};
+
+    // The kernel might have been built without scheduler statistics.
+    if !std::path::Path::new("/proc/self/schedstat").exists() {
+        return;
+    }
+    let mut s = sysinfo::System::new();
+    let pid = get_current_pid().expect("failed to get current pid");
+    s.refresh_process(pid);
+    let stats = s
+        .get_process(pid)
+        .expect("failed to get process")
+        .scheduler_stats();
+    // The scheduler statistics are only read if the details are enabled.
+    assert_eq!(stats.total_cpu_time, 0);
+
+    s.set_process_details(true);
+    s.refresh_process(pid);
+    let stats = s
+        .get_process(pid)
+        .expect("failed to get process")
+        .scheduler_stats();
+    assert!(stats.total_cpu_time > 0);
+    assert!(stats.total_timeslices > 0);
+    assert!(stats.cpu_time <= stats.total_cpu_time);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_threads() {
+    use std::sync::mpsc::channel;
+    use sysinfo::{get_current_pid, SystemExt};
+
+    let (started_tx, started_rx) = channel();
+    let (stop_tx, stop_rx) = channel::<()>();
+    let handle = std::thread::Builder::new()
+        .name("sysinfo-thread".to_owned())
+        .spawn(move || {
+            started_tx.send(()).unwrap();
+            stop_rx.recv().ok();
+        })
+        .unwrap();
+    started_rx.recv().unwrap();
+
+    let pid = get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get process");
+    // The main thread has the same id as its process.
+    assert!(p.threads().contains_key(&pid));
+    let tid = p
+        .threads()
+        .values()
+        .find(|t| t.name() == "sysinfo-thread")
+        .map(|t| t.tid())
+        .expect("thread not found");
+
+    stop_tx.send(()).unwrap();
+    handle.join().unwrap();
+    assert!(s.refresh_threads(pid));
+    let p = s.get_process(pid).expect("failed to get process");
+    assert!(!p.threads().contains_key(&tid));
+    assert!(!s.refresh_threads(-1));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_find_processes() {
+    use sysinfo::{ProcessQuery, ProcessStatus};
+
+    let mut p = std::process::Command::new("sleep")
+        .arg("3.5")
+        .spawn()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(250));
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let pid = p.id() as sysinfo::Pid;
+    let parent = sysinfo::get_current_pid().expect("failed to get current pid");
+
+    let find = |query: &ProcessQuery| s.find_processes(query).map(|p| p.pid()).collect::<Vec<_>>();
+
+    assert_eq!(find(&ProcessQuery::new().cmd("sleep 3.?")), &[pid]);
+    assert_eq!(find(&ProcessQuery::new().name("sleep").arg("3.5")), &[pid]);
+    assert_eq!(
+        find(&ProcessQuery::new().parent(parent).cmd("*3.5")),
+        &[pid]
+    );
+    assert_eq!(
+        find(&ProcessQuery::new().arg("3.5").status(ProcessStatus::Sleep)),
+        &[pid]
+    );
+    let process = s.get_process(pid).unwrap();
+    let query = ProcessQuery::new()
+        .exe(process.exe())
+        .uid(process.user_id().unwrap())
+        .cgroup(process.cgroup())
+        .parent(parent);
+    assert_eq!(find(&query), &[pid]);
+    assert!(find(&ProcessQuery::new().arg("sleep"))
+        .iter()
+        .all(|p| *p != pid));
+    assert!(find(
+        &ProcessQuery::new()
+            .cmd("sleep 3.5")
+            .and(ProcessQuery::new().parent(pid))
+    )
+    .is_empty());
+    assert_eq!(
+        find(&(!ProcessQuery::new().arg("3.5")).or(ProcessQuery::new().parent(parent)))
+            .contains(&pid),
+        true
+    );
+    assert!(find(&!ProcessQuery::new().arg("3.5").parent(parent))
+        .iter()
+        .all(|p| *p != pid));
+    assert_eq!(find(&ProcessQuery::new()).len(), s.get_processes().len());
+    p.kill().unwrap();
+    p.wait().unwrap();
+}
+
+#[test]
+#[cfg(all(target_os = "linux", feature = "regex"))]
+fn test_find_processes_regex() {
+    use sysinfo::ProcessQuery;
+
+    let mut p = std::process::Command::new("sleep")
+        .arg("3.25")
+        .spawn()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(250));
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+
+    let query = ProcessQuery::new().cmd_regex(regex::Regex::new(r"^sleep 3\.2\d$").unwrap());
+    let pids = s
+        .find_processes(&query)
+        .map(|p| p.pid())
+        .collect::<Vec<_>>();
+    assert_eq!(pids, &[p.id() as sysinfo::Pid]);
+    p.kill().unwrap();
+    p.wait().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_exe_info() {
+    let mut s = sysinfo::System::new();
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    s.refresh_process(pid);
+    let process = s.get_process(pid).expect("failed to get current process");
+    assert!(!process.is_exe_deleted());
+
+    let info = process.exe_info().expect("failed to get executable info");
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(info.class, sysinfo::ElfClass::Elf64);
+    } else {
+        assert_eq!(info.class, sysinfo::ElfClass::Elf32);
+    }
+    assert_eq!(info.architecture(), std::env::consts::ARCH);
+    assert_eq!(info.statically_linked, info.interpreter.is_none());
+}
+
+#[test]
+#[cfg(all(target_os = "linux", not(target_os = "android")))]
+fn test_process_exe_deleted() {
+    let dir = tempfile::tempdir().expect("failed to create temporary directory");
+    let exe = dir.path().join("sysinfo-sleep");
+    let spawn = || {
+        std::fs::copy("/bin/sleep", &exe).expect("failed to copy sleep");
+        let child = std::process::Command::new(&exe).arg("3").spawn().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(250));
+        child
+    };
+    let check = |child: &mut std::process::Child, expected: bool| {
+        let mut s = sysinfo::System::new();
+        let pid = child.id() as sysinfo::Pid;
+        s.refresh_process(pid);
+        assert_eq!(s.get_process(pid).unwrap().is_exe_deleted(), expected);
+        child.kill().unwrap();
+        child.wait().unwrap();
+    };
+
+    let mut child = spawn();
+    check(&mut child, false);
+
+    let mut child = spawn();
+    std::fs::remove_file(&exe).unwrap();
+    check(&mut child, true);
+
+    // Replacing the file, like a package manager does during an upgrade.
+    let mut child = spawn();
+    std::fs::copy("/bin/sleep", dir.path().join("new")).unwrap();
+    std::fs::rename(dir.path().join("new"), &exe).unwrap();
+    check(&mut child, true);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_namespace_pids() {
+    let mut s = sysinfo::System::new();
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    s.refresh_process(pid);
+    let process = s.get_process(pid).expect("failed to get current process");
+    if process.namespace_pids().is_empty() {
+        // Not supported by this kernel.
+        return;
+    }
+    assert_eq!(process.namespace_pids()[0], pid);
+    assert_eq!(process.namespace_tgids()[0], pid);
+    assert_eq!(
+        process.namespace_pgids().len(),
+        process.namespace_pids().len()
+    );
+    assert_eq!(
+        process.namespace_sids().len(),
+        process.namespace_pids().len()
+    );
+    let depth = process.namespace_pids().len();
+    let host_namespace = process.pid_namespace();
+
+    // We create a new PID namespace if we're allowed to.
+    let mut child = match std::process::Command::new("unshare")
+        .args(&["--pid", "--fork", "--kill-child", "sleep", "3"])
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return,
+    };
+    std::thread::sleep(std::time::Duration::from_millis(250));
+    s.refresh_processes();
+    let forked = s
+        .get_processes()
+        .values()
+        .find(|p| p.parent() == Some(child.id() as sysinfo::Pid) && p.name() == "sleep");
+    if let Some(forked) = forked {
+        assert_eq!(forked.namespace_pids().len(), depth + 1);
+        assert_eq!(forked.namespace_pids()[0], forked.pid());
+        assert_eq!(forked.container_pid(), Some(1));
+        let namespace = forked.pid_namespace().expect("failed to get PID namespace");
+        assert_ne!(Some(namespace), host_namespace);
+        assert_eq!(
+            s.get_process_by_container_pid(namespace, 1)
+                .map(|p| p.pid()),
+            Some(forked.pid())
+        );
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_io_priority() {
+    use sysinfo::{IoPriority, IoPriorityClass};
+
+    let mut child = std::process::Command::new("sleep")
+        .arg("3")
+        .spawn()
+        .unwrap();
+    let pid = child.id() as sysinfo::Pid;
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let process = s.get_process(pid).expect("failed to get child process");
+    assert!(process.io_priority().is_some());
+
+    let idle = IoPriority {
+        class: IoPriorityClass::Idle,
+        level: 0,
+    };
+    assert!(process.set_io_priority(idle).is_ok());
+    assert_eq!(process.io_priority(), Some(idle));
+    let best_effort = IoPriority {
+        class: IoPriorityClass::BestEffort,
+        level: 6,
+    };
+    assert!(process.set_io_priority(best_effort).is_ok());
+    let invalid = IoPriority {
+        class: IoPriorityClass::BestEffort,
+        level: 8,
+    };
+    assert_eq!(
+        process.set_io_priority(invalid).map_err(|e| e.kind()),
+        Err(std::io::ErrorKind::InvalidInput)
+    );
+    assert_eq!(process.io_priority(), Some(best_effort));
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_monitor_command() {
+    use std::process::Command;
+    use std::time::Duration;
+
+    let mut command = Command::new("sh");
+    command.args(&["-c", "sleep 0.3; exit 3"]);
+    let report = sysinfo::monitor_command(&mut command, Duration::from_millis(50))
+        .expect("failed to run command");
+    assert_eq!(report.status.code(), Some(3));
+    assert!(report.elapsed >= Duration::from_millis(300));
+    assert!(!report.samples.is_empty());
+    assert!(report.peak_threads() >= 1);
+    assert!(report.resource_usage.max_resident_memory > 0);
+    assert!(report.to_string().ends_with("Exit status: 3"));
+
+    // The memory used by `dd` is reported by the kernel even if it exits before being sampled.
+    let mut command = Command::new("dd");
+    command.args(&["if=/dev/zero", "of=/dev/null", "bs=8M", "count=1"]);
+    command.stderr(std::process::Stdio::null());
+    let report = sysinfo::monitor_command(&mut command, Duration::from_millis(50))
+        .expect("failed to run command");
+    assert!(report.status.success());
+    assert!(report.resource_usage.max_resident_memory >= 8 * 1024);
+
+    // The piped output is discarded, otherwise the command would block once the pipe is full.
+    let mut command = Command::new("dd");
+    command.args(&["if=/dev/zero", "bs=1M", "count=4"]);
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    let report = sysinfo::monitor_command(&mut command, Duration::from_millis(50))
+        .expect("failed to run command");
+    assert!(report.status.success());
+
+    assert!(sysinfo::monitor_command(
+        &mut Command::new("/does/not/exist"),
+        Duration::from_millis(50)
+    )
+    .is_err());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_current_process() {
+    let before = sysinfo::current_process().expect("failed to get current process");
+    assert!(before.memory > 0);
+    assert!(before.peak_memory >= before.memory);
+    assert!(before.virtual_memory >= before.memory);
+    assert!(before.nb_threads >= 1);
+    // At least stdin, stdout and stderr.
+    assert!(before.nb_fds >= 3);
+
+    let file = std::fs::File::open("/proc/self/status").unwrap();
+    let thread = std::thread::spawn(|| {
+        std::thread::sleep(std::time::Duration::from_millis(200));
+    });
+    let cpu_time = |p: &sysinfo::CurrentProcess| p.user_time + p.system_time;
+    // Getting the snapshot uses some CPU time, so it'll end up increasing.
+    let start = std::time::Instant::now();
+    let mut after = sysinfo::current_process().expect("failed to get current process");
+    while cpu_time(&after) == cpu_time(&before) && start.elapsed().as_secs() < 5 {
+        after = sysinfo::current_process().expect("failed to get current process");
+    }
+    assert!(cpu_time(&after) > cpu_time(&before));
+    // Other tests may run at the same time so we can only check lower bounds.
+    assert!(after.nb_threads >= 2);
+    assert!(after.nb_fds >= 4);
+    drop(file);
+    thread.join().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_raw_signals() {
+    use std::os::unix::process::{CommandExt, ExitStatusExt};
+    use std::process::Command;
+
+    let first = sysinfo::realtime_signal(0).expect("no realtime signal");
+    assert!(first > 31);
+    assert_eq!(sysinfo::realtime_signal(1), Some(first + 1));
+    assert!(sysinfo::realtime_signal(-1).is_none());
+    assert!(sysinfo::realtime_signal(1000).is_none());
+
+    // Spawns `args` in a new process group (or session) and waits for it to have `members`
+    // processes.
+    let spawn = |args: &[&str], setsid: bool, members: usize| {
+        let mut command = Command::new("sh");
+        command.arg("-c").args(args);
+        unsafe {
+            command.pre_exec(move || {
+                if setsid {
+                    libc::setsid();
+                } else {
+                    libc::setpgid(0, 0);
+                }
+                Ok(())
+            });
+        }
+        let child = command.spawn().unwrap();
+        let pid = child.id() as sysinfo::Pid;
+        let mut s = sysinfo::System::new();
+        for _ in 0..50 {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            s.refresh_processes();
+            let count = s
+                .get_processes()
+                .values()
+                .filter(|p| p.group_id() == pid)
+                .count();
+            if count >= members {
+                break;
+            }
+        }
+        (child, s)
+    };
+
+    // Realtime signal with a payload: the default action is to terminate the process.
+    let signal = sysinfo::realtime_signal(2).unwrap();
+    let (mut child, s) = spawn(&["sleep 3"], false, 1);
+    let process = s.get_process(child.id() as sysinfo::Pid).unwrap();
+    assert!(process.sigqueue(signal, 42));
+    assert_eq!(child.wait().unwrap().signal(), Some(signal));
+
+    // Process group.
+    let (mut child, s) = spawn(&["sleep 3 & sleep 3; wait"], false, 3);
+    let pid = child.id() as sysinfo::Pid;
+    let process = s.get_process(pid).unwrap();
+    assert_eq!(process.group_id(), pid);
+    let members = s
+        .get_processes()
+        .values()
+        .filter(|p| p.group_id() == pid)
+        .count();
+    assert_eq!(members, 3);
+    assert!(process.kill_group(libc::SIGKILL));
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+
+    // Session.
+    let (mut child, s) = spawn(&["sleep 3 & sleep 3; wait"], true, 3);
+    let pid = child.id() as sysinfo::Pid;
+    assert_eq!(s.get_process(pid).unwrap().session_id(), pid);
+    assert_eq!(s.kill_session(pid, libc::SIGKILL), 3);
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+
+    let (mut child, s) = spawn(&["sleep 3"], false, 1);
+    assert!(s
+        .get_process(child.id() as sysinfo::Pid)
+        .unwrap()
+        .kill_raw(libc::SIGTERM));
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGTERM));
+}
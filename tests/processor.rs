@@ -32,3 +32,84 @@ fn test_physical_core_numbers() {
     assert_ne!(count, None);
     assert!(count.unwrap() > 0);
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_kernel_stats() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    let stats = s.get_kernel_stats();
+    assert!(stats.get_total_context_switches() > 0);
+    // At least the current thread is running.
+    assert!(stats.get_running_processes() > 0);
+    let forks = stats.get_total_forks();
+
+    std::process::Command::new("true")
+        .status()
+        .expect("failed to run `true`");
+    s.refresh_cpu();
+    assert!(s.get_kernel_stats().get_total_forks() > forks);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_interrupts() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    assert!(s.get_interrupts().is_empty());
+    s.refresh_interrupts();
+    let nb_processors = s.get_processors().len();
+    assert!(!s.get_interrupts().is_empty());
+    assert!(s
+        .get_interrupts()
+        .iter()
+        .all(|i| i.get_total_counts().len() == nb_processors));
+    assert!(s
+        .get_softirqs()
+        .iter()
+        .any(|i| i.get_name() == "TIMER" && i.get_total_count() > 0));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_numa_nodes() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    s.refresh_cpu();
+    // The list is empty if NUMA isn't supported by the kernel.
+    for node in s.get_numa_nodes() {
+        assert!(node.memory.free <= node.memory.total);
+        assert!(s.get_numa_node_processors(&node).len() <= node.cpus.len());
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pressure() {
+    use std::time::Duration;
+    use sysinfo::{PressureResource, PressureStallKind, PressureTrigger, SystemExt};
+
+    let s = sysinfo::System::new();
+    let pressure = match s.get_pressure(PressureResource::Cpu) {
+        Some(pressure) => pressure,
+        // PSI isn't available.
+        None => return,
+    };
+    assert!(pressure.some.is_some());
+    assert!(s
+        .get_cgroup_pressure("/does/not/exist", PressureResource::Cpu)
+        .is_none());
+
+    // Creating triggers may not be allowed (in a container for example).
+    if let Ok(trigger) = PressureTrigger::new(
+        PressureResource::Cpu,
+        PressureStallKind::Some,
+        Duration::from_millis(50),
+        Duration::from_millis(500),
+    ) {
+        assert!(trigger.wait(Some(Duration::from_millis(10))).is_ok());
+    }
+}
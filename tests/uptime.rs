@@ -12,3 +12,70 @@ fn test_uptime() {
     s.refresh_all();
     assert!(s.get_uptime() != 0);
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_boot_identity() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    let boot_id = s.get_boot_id().expect("failed to read boot id");
+    // It is an UUID: "66fc49b7-8f85-40ce-a760-5a9600468ffd".
+    assert_eq!(boot_id.len(), 36);
+    assert_eq!(s.get_boot_id(), Some(boot_id));
+    if let Some(machine_id) = s.get_machine_id() {
+        assert_eq!(machine_id.len(), 32);
+    }
+
+    let idle_time = s.get_idle_time();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    s.refresh_cpu();
+    assert!(s.get_idle_time() >= idle_time);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_detailed_load_average() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    let load = s.get_detailed_load_average();
+    // At least the current thread is runnable.
+    assert!(load.runnable > 0);
+    assert!(load.total >= load.runnable);
+    assert!(load.pid_max > load.last_pid);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_memory_details() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    s.refresh_memory();
+    let info = s.get_memory_info();
+    assert!(info.total > 0);
+    assert_eq!(info.total, s.get_total_memory());
+    assert_eq!(info.swap_total, s.get_total_swap());
+
+    s.refresh_swaps();
+    if s.get_swaps().is_empty() {
+        assert_eq!(s.get_total_swap(), 0);
+    }
+
+    s.refresh_vmstat();
+    let page_faults = s.get_vmstat().get_total_page_faults();
+    assert!(page_faults > 0);
+    // Touching new memory generates page faults.
+    let data = vec![1u8; 16 * 1024 * 1024];
+    assert_eq!(data.iter().map(|x| *x as usize).sum::<usize>(), data.len());
+    s.refresh_vmstat();
+    assert!(s.get_vmstat().get_total_page_faults() > page_faults);
+
+    s.refresh_cgroup_limits();
+    assert!(s.get_effective_memory_limit() <= s.get_total_memory());
+    assert!(s.get_effective_cpu_quota() > 0.);
+    s.set_cgroup_constrained(true);
+    s.refresh_memory();
+    assert!(s.get_used_memory() <= s.get_total_memory());
+}